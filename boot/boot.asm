@@ -21,20 +21,32 @@ start:
     call print_string
 
     ; Load kernel from disk to 0x8000
-    mov ah, 0x02
-    mov al, 128          ; Try loading fewer sectors first (load 128 sectors, bytes 64KB)
-    mov ch, 0
-    mov cl, 2 ; Load from sector 2
-    mov dh, 0
+    ; Liczbę sektorów jądra wpisuje mkimage (kernel_sectors). Czytamy LBA
+    ; (int 0x13, AH=0x42) porcjami po 64 sektory - jedna porcja to 32 KB,
+    ; więc segment bufora przesuwamy o 0x800 bez przekraczania 64 KB.
+    cmp word [kernel_sectors], 0
+    je disk_error
+.read_chunk:
+    mov ax, [kernel_sectors]
+    cmp ax, 64
+    jbe .read_count
+    mov ax, 64
+.read_count:
+    mov [dap_count], ax
+    mov si, dap
+    mov ah, 0x42
     mov dl, [boot_drive]
-    mov bx, 0x8000
-    mov di, 60            ; Count sectors load (60)
     int 0x13
+    jc disk_error
 
+    mov ax, [dap_count]
+    add [dap_lba], ax
+    shl ax, 5               ; Sektory -> paragrafy (512 / 16)
+    add [dap_segment], ax
+    mov ax, [dap_count]
+    sub [kernel_sectors], ax
+    jnz .read_chunk
 
-    ; jc disk_error
-    ; Don't check carry - QEMU sometimes sets it incorrectly
-    
     ; Show that we loaded OK
     mov si, ok_msg
     call print_string
@@ -204,5 +216,18 @@ ok_msg: db 'K', 0
 error_msg: db 'Disk error!', 0
 boot_drive: db 0
 
+; Disk Address Packet dla int 0x13 AH=0x42 - jądro od LBA 1 pod 0x0800:0000
+dap:
+    db 0x10, 0
+dap_count: dw 0
+    dw 0
+dap_segment: dw 0x0800
+dap_lba: dd 1, 0
+
+; Offset 444 (tuż przed tablicą partycji) - mkimage wpisuje tu rozmiar
+; jądra w sektorach; 0 = obraz nie z mkimage
+times 444-($-$$) db 0
+kernel_sectors: dw 0
+
 times 510-($-$$) db 0
 dw 0xAA55
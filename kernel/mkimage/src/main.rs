@@ -0,0 +1,337 @@
+// =============================================================================
+// MKIMAGE - budowanie os.img na hoście
+// =============================================================================
+// Układ obrazu:
+// LBA 0          - boot sector (boot.bin) + tablica partycji MBR
+// LBA 1..        - jądro; liczbę sektorów mkimage wpisuje do boot sectora
+//                  (offset 444), bootloader czyta tyle pod 0x8000
+// LBA 2048..     - partycja FAT16 z plikami skopiowanymi z katalogu hosta
+//
+// Sterownik FAT16 i RamDisk to te same pliki co w jądrze (`#[path]`), więc
+// obraz jest zapisany dokładnie tak, jak jądro go potem czyta.
+// Użycie:
+//   mkimage --boot boot.bin --kernel kernel.bin [--files DIR] [--size-mb N] -o os.img
+//   mkimage --check os.img [--repair]     (fsck partycji FAT16 w gotowym obrazie)
+// =============================================================================
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+macro_rules! log_error {
+    ($($arg:tt)*) => { eprintln!("error: {}", format_args!($($arg)*)) };
+}
+
+macro_rules! log_warn {
+    ($($arg:tt)*) => { eprintln!("warning: {}", format_args!($($arg)*)) };
+}
+
+macro_rules! log_info {
+    ($($arg:tt)*) => { println!("{}", format_args!($($arg)*)) };
+}
+
+// Wspólny kod z jądrem - narzędzie używa tylko części API
+#[allow(dead_code)]
+#[path = "../../src/fs/block.rs"]
+pub mod kernel_block;
+
+#[allow(dead_code)]
+#[path = "../../src/fs/fat16/volume.rs"]
+pub mod kernel_fat16;
+
+#[allow(dead_code)]
+#[path = "../../src/time/date.rs"]
+pub mod kernel_date;
+
+// Te same ścieżki `crate::fs::...` i `crate::time::...` co w jądrze
+mod time {
+    pub use crate::kernel_date as date;
+}
+
+mod fs {
+    pub use crate::kernel_block as block;
+
+    pub mod fat16 {
+        pub use crate::kernel_fat16::*;
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+use fs::block::{mbr_partitions, Partition, RamDisk};
+use fs::fat16::{CheckMode, CheckScratch, Dir, Fat16Geometry, Fat16Volume, FileName};
+use time::date::DateTime;
+
+const SECTOR_SIZE: usize = 512;
+const PARTITION_START: usize = 2048;   // 1 MiB, jak fdisk
+// Jądro ładowane w trybie rzeczywistym od 0x8000 do 0x80000 - wyżej może
+// leżeć EBDA BIOS-u. To mniej niż miejsce przed partycją (LBA 1..2048).
+const KERNEL_LOAD_ADDRESS: usize = 0x8000;
+const KERNEL_LOAD_END: usize = 0x80000;
+const KERNEL_MAX_SECTORS: usize = (KERNEL_LOAD_END - KERNEL_LOAD_ADDRESS) / SECTOR_SIZE;
+const _: () = assert!(KERNEL_MAX_SECTORS < PARTITION_START);
+const DEFAULT_SIZE_MB: usize = 16;
+const MAX_SIZE_MB: usize = 1024;       // Powyżej brakuje numerów klastrów FAT16
+const MIN_SIZE_MB: usize = 4;          // Poniżej 4085 klastrów to już FAT12
+
+const MBR_TABLE: usize = 446;
+const BOOT_KERNEL_SECTORS: usize = 444; // `kernel_sectors` w boot/boot.asm (u16)
+const PARTITION_FAT16_SMALL: u8 = 0x04; // FAT16 < 32 MB
+const PARTITION_FAT16: u8 = 0x06;
+
+enum Command {
+    Build(Options),
+    Check { image: PathBuf, mode: CheckMode },
+}
+
+struct Options {
+    boot: PathBuf,
+    kernel: PathBuf,
+    files: Option<PathBuf>,
+    size_mb: usize,
+    output: PathBuf,
+}
+
+fn usage() -> String {
+    "usage: mkimage --boot boot.bin --kernel kernel.bin [--files DIR] [--size-mb N] -o os.img\n       \
+     mkimage --check os.img [--repair]".into()
+}
+
+fn parse_args() -> Result<Command, String> {
+    let mut boot = None;
+    let mut kernel = None;
+    let mut files = None;
+    let mut size_mb = DEFAULT_SIZE_MB;
+    let mut output = None;
+    let mut check = None;
+    let mut mode = CheckMode::Report;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--boot" => boot = Some(PathBuf::from(value()?)),
+            "--kernel" => kernel = Some(PathBuf::from(value()?)),
+            "--files" => files = Some(PathBuf::from(value()?)),
+            "--size-mb" => {
+                size_mb = value()?.parse().map_err(|_| "--size-mb expects a number".to_string())?;
+            }
+            "-o" | "--output" => output = Some(PathBuf::from(value()?)),
+            "--check" => check = Some(PathBuf::from(value()?)),
+            "--repair" => mode = CheckMode::Repair,
+            "-h" | "--help" => return Err(usage()),
+            other => return Err(format!("unknown argument {}\n{}", other, usage())),
+        }
+    }
+
+    if let Some(image) = check {
+        return Ok(Command::Check { image, mode });
+    }
+    if !(MIN_SIZE_MB..=MAX_SIZE_MB).contains(&size_mb) {
+        return Err(format!("--size-mb must be between {} and {}", MIN_SIZE_MB, MAX_SIZE_MB));
+    }
+    Ok(Command::Build(Options {
+        boot: boot.ok_or_else(usage)?,
+        kernel: kernel.ok_or_else(usage)?,
+        files,
+        size_mb,
+        output: output.ok_or_else(usage)?,
+    }))
+}
+
+// Najmniejszy klaster, przy którym liczba klastrów mieści się w FAT16
+fn geometry_for(total_sectors: usize) -> Fat16Geometry {
+    let mut sectors_per_cluster = 1;
+    while total_sectors / sectors_per_cluster > 65000 {
+        sectors_per_cluster *= 2;
+    }
+    Fat16Geometry {
+        bytes_per_sector: SECTOR_SIZE as u16,
+        sectors_per_cluster: sectors_per_cluster as u8,
+        reserved_sectors: 1,
+        fat_count: 2,
+        root_entries: 512,
+        total_sectors: total_sectors as u32,
+    }
+}
+
+// Wpis partycji LBA (pola CHS ustawione na "nieznane", jak robią to fdisk/parted)
+fn write_partition_entry(sector: &mut [u8], kind: u8, start: usize, count: usize) {
+    let entry = &mut sector[MBR_TABLE..MBR_TABLE + 16];
+    entry.fill(0);
+    entry[1..4].copy_from_slice(&[0xFE, 0xFF, 0xFF]);
+    entry[4] = kind;
+    entry[5..8].copy_from_slice(&[0xFE, 0xFF, 0xFF]);
+    entry[8..12].copy_from_slice(&(start as u32).to_le_bytes());
+    entry[12..16].copy_from_slice(&(count as u32).to_le_bytes());
+}
+
+// Zegar woluminu dla kopiowanych plików. Bez biblioteki stref czasowych
+// zapisujemy UTC (FAT zakłada czas lokalny - na hoście w UTC to jedno).
+fn host_now() -> Option<DateTime> {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    let (days, rest) = ((secs / 86400) as i64, secs % 86400);
+
+    // Dni od 1970-01-01 -> data (algorytm "civil from days", ery po 400 lat)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153; // Miesiąc liczony od marca
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    Some(DateTime {
+        year: u16::try_from(year).ok()?,
+        month: month as u8,
+        day: (day_of_year - (153 * mp + 2) / 5 + 1) as u8,
+        hour: (rest / 3600) as u8,
+        minute: (rest / 60 % 60) as u8,
+        second: (rest % 60) as u8,
+    })
+}
+
+// Kopiuje zawartość katalogu hosta `source` do katalogu `dir` woluminu;
+// podkatalogi hosta stają się podkatalogami FAT16 (rekurencyjnie)
+fn copy_files<D: fs::block::BlockDevice>(volume: &mut Fat16Volume<D>, dir: Dir, source: &Path) -> Result<(), String> {
+    let mut entries: Vec<_> = std::fs::read_dir(source)
+        .map_err(|e| format!("{}: {}", source.display(), e))?
+        .filter_map(Result::ok)
+        .collect();
+    entries.sort_by_key(|entry| entry.file_name());
+
+    // FAT nie rozróżnia wielkości liter - "README.md" i "readme.md" to ten sam plik
+    let mut written: Vec<String> = Vec::new();
+    for entry in entries {
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        if !path.is_file() && !path.is_dir() {
+            log_warn!("{}: not a regular file or directory, skipped", path.display());
+            continue;
+        }
+        // Nazwa hosta trafia do LFN bez zmian ("Moje notatki.txt"), alias 8.3
+        // wylicza sterownik; odpada tylko to, czego FAT nie dopuszcza
+        let chars: Vec<u16> = file_name.encode_utf16().collect();
+        let Ok(name) = FileName::from_ucs2(&chars) else {
+            log_warn!("{}: not a valid FAT file name, skipped", file_name);
+            continue;
+        };
+        let folded = file_name.to_ascii_uppercase();
+        if written.contains(&folded) {
+            log_warn!("{}: differs from an earlier file only in case, skipped", file_name);
+            continue;
+        }
+        written.push(folded);
+
+        if path.is_dir() {
+            let subdir = volume.mkdir(dir, &name).map_err(|e| format!("{}: {}", path.display(), e))?;
+            log_info!("  {}/", path.display());
+            copy_files(volume, subdir, &path)?;
+            continue;
+        }
+        let data = std::fs::read(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        volume.save(dir, &name, &data).map_err(|e| format!("{}: {}", path.display(), e))?;
+        log_info!("  {} ({} B)", path.display(), data.len());
+    }
+    Ok(())
+}
+
+fn build(options: &Options) -> Result<(), String> {
+    let read = |path: &Path| std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e));
+    let boot = read(&options.boot)?;
+    let kernel = read(&options.kernel)?;
+
+    if boot.len() != SECTOR_SIZE || boot[510..512] != [0x55, 0xAA] {
+        return Err(format!("{}: boot sector must be 512 bytes ending with 55 AA", options.boot.display()));
+    }
+    if boot[MBR_TABLE..510].iter().any(|&b| b != 0) {
+        return Err(format!("{}: boot code overlaps the partition table", options.boot.display()));
+    }
+    let kernel_sectors = kernel.len().div_ceil(SECTOR_SIZE);
+    if kernel_sectors > KERNEL_MAX_SECTORS {
+        return Err(format!(
+            "kernel has {} sectors, the bootloader loads only {}", kernel_sectors, KERNEL_MAX_SECTORS
+        ));
+    }
+
+    let partition_sectors = options.size_mb * 1024 * 1024 / SECTOR_SIZE;
+    let mut image = vec![0u8; (PARTITION_START + partition_sectors) * SECTOR_SIZE];
+
+    // 1. Boot sector z tablicą partycji i rozmiarem jądra, 2. jądro zaraz za nim
+    image[..SECTOR_SIZE].copy_from_slice(&boot);
+    let kind = if partition_sectors < 0x10000 { PARTITION_FAT16_SMALL } else { PARTITION_FAT16 };
+    write_partition_entry(&mut image[..SECTOR_SIZE], kind, PARTITION_START, partition_sectors);
+    image[BOOT_KERNEL_SECTORS..MBR_TABLE].copy_from_slice(&(kernel_sectors as u16).to_le_bytes());
+    image[SECTOR_SIZE..SECTOR_SIZE + kernel.len()].copy_from_slice(&kernel);
+
+    // 3. Partycja FAT16 - ten sam kod formatowania co RAMDISK w jądrze
+    {
+        let disk = Partition::new(RamDisk::new(&mut image), PARTITION_START as u64, partition_sectors as u64)
+            .ok_or("partition does not fit in the image")?;
+        let mut volume = Fat16Volume::format(disk, &geometry_for(partition_sectors))
+            .map_err(|e| format!("formatting the FAT16 partition failed: {}", e))?;
+        volume.set_clock(host_now);
+        if let Some(dir) = &options.files {
+            copy_files(&mut volume, Dir::ROOT, dir)?;
+        }
+        let mut scratch = CheckScratch::EMPTY;
+        let report = volume.check(CheckMode::Report, &mut scratch).map_err(|e| format!("checking the new volume failed: {}", e))?;
+        if !report.is_clean() {
+            return Err(format!("new volume is inconsistent: {}", report));
+        }
+    }
+
+    std::fs::write(&options.output, &image).map_err(|e| format!("{}: {}", options.output.display(), e))?;
+    log_info!(
+        "{}: kernel {} sectors, FAT16 {} MB at LBA {}",
+        options.output.display(), kernel_sectors, options.size_mb, PARTITION_START
+    );
+    Ok(())
+}
+
+// Tak jak jądro: najpierw cały obraz (mkfs.fat), potem partycje FAT16 z MBR
+fn mount_image(image: &mut [u8]) -> Option<Fat16Volume<Partition<RamDisk<'_>>>> {
+    let sectors = (image.len() / SECTOR_SIZE) as u64;
+    let (start, count) = if Fat16Volume::mount(Partition::whole(RamDisk::new(image))).is_ok() {
+        (0, sectors)
+    } else {
+        let partition = mbr_partitions(&mut RamDisk::new(image)).into_iter().flatten().find(|p| p.is_fat16())?;
+        (partition.start, partition.count)
+    };
+    Fat16Volume::mount(Partition::new(RamDisk::new(image), start, count)?).ok()
+}
+
+fn check(image_path: &Path, mode: CheckMode) -> Result<(), String> {
+    let mut image = std::fs::read(image_path).map_err(|e| format!("{}: {}", image_path.display(), e))?;
+    let report = {
+        let mut volume = mount_image(&mut image).ok_or("no FAT16 volume found in the image")?;
+        let mut scratch = CheckScratch::EMPTY;
+        volume.check(mode, &mut scratch).map_err(|e| format!("reading the volume failed: {}", e))?
+    };
+    log_info!("{}: {}", image_path.display(), report);
+
+    if mode == CheckMode::Repair {
+        if !report.is_clean() {
+            std::fs::write(image_path, &image).map_err(|e| format!("{}: {}", image_path.display(), e))?;
+            log_info!("{} errors repaired", report.errors());
+        }
+        return Ok(());
+    }
+    if report.is_clean() { Ok(()) } else { Err("volume has errors, run with --repair".into()) }
+}
+
+fn main() -> ExitCode {
+    let result = parse_args().and_then(|command| match command {
+        Command::Build(options) => build(&options),
+        Command::Check { image, mode } => check(&image, mode),
+    });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            log_error!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
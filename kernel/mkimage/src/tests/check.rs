@@ -0,0 +1,162 @@
+// Fsck: każde uszkodzenie robione ręcznie w surowym obrazie, potem raport,
+// naprawa i drugi raport, który musi być czysty
+
+use super::*;
+use crate::fs::fat16::{CheckMode, CheckReport, CheckScratch};
+
+fn check(image: &mut [u8], mode: CheckMode) -> CheckReport {
+    let mut scratch = CheckScratch::EMPTY;
+    mount(image).check(mode, &mut scratch).expect("check")
+}
+
+// Obraz z plikami A.TXT (3 klastry) i B.TXT (2 klastry), wpisy 0 i 1
+fn image_with_files() -> Vec<u8> {
+    let mut image = formatted_image();
+    {
+        let mut volume = mount(&mut image);
+        volume.save(Dir::ROOT, &name("A.TXT"), &pattern(3 * SECTOR_SIZE)).unwrap();
+        volume.save(Dir::ROOT, &name("B.TXT"), &pattern(2 * SECTOR_SIZE)).unwrap();
+    }
+    assert!(check(&mut image, CheckMode::Report).is_clean());
+    image
+}
+
+fn first_cluster(image: &mut [u8], index: usize) -> u16 {
+    get_u16(root_entry(image, index), ENTRY_FIRST_CLUSTER)
+}
+
+// Naprawa zwraca te same liczby co raport, a po niej wolumin jest czysty
+fn repair_and_recheck(image: &mut [u8], found: &CheckReport) {
+    let repaired = check(image, CheckMode::Repair);
+    assert_eq!(format!("{}", repaired), format!("{}", found));
+    let after = check(image, CheckMode::Report);
+    assert!(after.is_clean(), "after repair: {}", after);
+}
+
+#[test]
+fn clean_volume_counts_files_and_directories() {
+    let mut image = image_with_files();
+    mount(&mut image).mkdir(Dir::ROOT, &name("DOCS")).unwrap();
+    let report = check(&mut image, CheckMode::Report);
+    assert!(report.is_clean(), "{}", report);
+    assert_eq!((report.files, report.directories), (2, 1));
+}
+
+#[test]
+fn fat_copies_that_disagree() {
+    let mut image = image_with_files();
+    set_fat_copy(&mut image, 1, 100, 0x1234);
+    set_fat_copy(&mut image, 1, 2000, FAT_EOC);
+
+    let report = check(&mut image, CheckMode::Report);
+    assert_eq!(report.fat_mismatches, 2); // Dwa różne sektory FAT
+    assert_eq!(report.errors(), 2);
+    repair_and_recheck(&mut image, &report);
+    // Wzorcem jest pierwsza kopia
+    assert_eq!(fat(&image)[100], 0);
+}
+
+#[test]
+fn cross_linked_files() {
+    let mut image = image_with_files();
+    // B.TXT wskazuje na drugi klaster A.TXT; jego własne klastry są zgubione
+    let shared = first_cluster(&mut image, 0) + 1;
+    root_entry(&mut image, 1)[ENTRY_FIRST_CLUSTER..ENTRY_FIRST_CLUSTER + 2].copy_from_slice(&shared.to_le_bytes());
+
+    let report = check(&mut image, CheckMode::Report);
+    assert_eq!(report.cross_links, 1);
+    assert_eq!(report.size_mismatches, 1); // B.TXT po ucięciu nie ma klastrów
+    assert_eq!(report.lost_clusters, 2);
+    assert_eq!(report.errors(), 4);
+    repair_and_recheck(&mut image, &report);
+
+    // A.TXT nietknięty, B.TXT pusty
+    let mut volume = mount(&mut image);
+    let mut buf = [0u8; 4096];
+    assert_eq!(volume.load(Dir::ROOT, &name("A.TXT"), &mut buf).unwrap(), 3 * SECTOR_SIZE);
+    assert_eq!(&buf[..3 * SECTOR_SIZE], &pattern(3 * SECTOR_SIZE)[..]);
+    assert_eq!(volume.load(Dir::ROOT, &name("B.TXT"), &mut buf).unwrap(), 0);
+}
+
+#[test]
+fn chain_with_a_loop() {
+    let mut image = image_with_files();
+    // Ostatni klaster A.TXT wraca na pierwszy
+    let first = first_cluster(&mut image, 0);
+    set_fat(&mut image, first + 2, first);
+
+    let report = check(&mut image, CheckMode::Report);
+    assert_eq!(report.bad_chains, 1);
+    assert_eq!(report.errors(), 1);
+    repair_and_recheck(&mut image, &report);
+    assert!(fat(&image)[first as usize + 2] >= 0xFFF8);
+}
+
+#[test]
+fn file_size_mismatch() {
+    let mut image = image_with_files();
+    // A.TXT: 3 klastry, ale file_size na 2; B.TXT: 2 klastry, file_size na 10
+    root_entry(&mut image, 0)[ENTRY_FILE_SIZE..ENTRY_FILE_SIZE + 4].copy_from_slice(&600u32.to_le_bytes());
+    root_entry(&mut image, 1)[ENTRY_FILE_SIZE..ENTRY_FILE_SIZE + 4].copy_from_slice(&5000u32.to_le_bytes());
+
+    let report = check(&mut image, CheckMode::Report);
+    assert_eq!(report.size_mismatches, 2);
+    assert_eq!(report.errors(), 2);
+    repair_and_recheck(&mut image, &report);
+
+    // Nadmiarowy klaster zwolniony, brakujące dane ucięte do długości łańcucha
+    assert_eq!(used_clusters(&image), 2 + 2);
+    let mut volume = mount(&mut image);
+    let mut buf = [0u8; 8192];
+    assert_eq!(volume.load(Dir::ROOT, &name("A.TXT"), &mut buf).unwrap(), 600);
+    assert_eq!(volume.load(Dir::ROOT, &name("B.TXT"), &mut buf).unwrap(), 2 * SECTOR_SIZE);
+}
+
+#[test]
+fn lost_clusters() {
+    let mut image = image_with_files();
+    set_fat(&mut image, 100, 101);
+    set_fat(&mut image, 101, FAT_EOC);
+    set_fat(&mut image, 200, FAT_EOC);
+
+    let report = check(&mut image, CheckMode::Report);
+    assert_eq!(report.lost_clusters, 3);
+    assert_eq!(report.errors(), 3);
+    repair_and_recheck(&mut image, &report);
+    assert_eq!(used_clusters(&image), 3 + 2);
+}
+
+#[test]
+fn repair_fixes_everything_at_once() {
+    let mut image = image_with_files();
+    let first = first_cluster(&mut image, 0);
+    set_fat(&mut image, first + 2, first);
+    set_fat(&mut image, 300, FAT_EOC);
+    set_fat_copy(&mut image, 1, 1000, 7);
+    root_entry(&mut image, 1)[ENTRY_FILE_SIZE..ENTRY_FILE_SIZE + 4].copy_from_slice(&100u32.to_le_bytes());
+
+    let report = check(&mut image, CheckMode::Report);
+    assert_eq!(
+        (report.fat_mismatches, report.bad_chains, report.size_mismatches, report.lost_clusters),
+        (1, 1, 1, 1)
+    );
+    repair_and_recheck(&mut image, &report);
+}
+
+#[test]
+fn duplicate_names() {
+    let mut image = image_with_files();
+    mount(&mut image).save(Dir::ROOT, &name("FSCK.TXT"), b"x").unwrap();
+    // B.TXT dostaje nazwę A.TXT; FSCK.TXT jest zajęty, więc naprawa bierze FSCK~1
+    root_entry(&mut image, 1)[..11].copy_from_slice(b"A       TXT");
+
+    let report = check(&mut image, CheckMode::Report);
+    assert_eq!(report.duplicate_names, 1);
+    assert_eq!(report.errors(), 1);
+    repair_and_recheck(&mut image, &report);
+
+    let mut volume = mount(&mut image);
+    assert_eq!(list(&mut volume, Dir::ROOT), ["A.TXT", "FSCK~1.TXT", "FSCK.TXT"]);
+    let mut buf = [0u8; 4096];
+    assert_eq!(volume.load(Dir::ROOT, &name("FSCK~1.TXT"), &mut buf).unwrap(), 2 * SECTOR_SIZE);
+}
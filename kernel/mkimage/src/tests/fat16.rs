@@ -0,0 +1,201 @@
+// Format, mount i zapis plików (bez fsck - ten ma osobne testy)
+
+use super::*;
+use crate::fs::fat16::{Fat16Error, Fat16Geometry};
+
+#[test]
+fn format_rejects_geometry_larger_than_device() {
+    let mut image = vec![0u8; 1024 * SECTOR_SIZE];
+    let result = Fat16Volume::format(RamDisk::new(&mut image), &geometry_for(IMAGE_SECTORS));
+    assert!(result.is_err());
+}
+
+#[test]
+fn mount_rejects_bad_bpb() {
+    // (opis, offset, nowe bajty) - każdy przypadek na świeżym obrazie
+    let reserved = get_u16(&formatted_image(), BPB_RESERVED_SECTORS) as usize;
+    let cases: &[(&str, usize, &[u8])] = &[
+        ("boot signature", 510, &[0x00, 0x00]),
+        ("sector size not a power of two", 11, &500u16.to_le_bytes()),
+        ("sector size differs from the device", 11, &1024u16.to_le_bytes()),
+        ("sectors per cluster not a power of two", 13, &[3]),
+        ("zero sectors per cluster", 13, &[0]),
+        ("zero reserved sectors", 14, &[0, 0]),
+        ("zero FAT count", 16, &[0]),
+        ("root directory not whole sectors", 17, &500u16.to_le_bytes()),
+        ("volume larger than the device", 19, &0xFFFFu16.to_le_bytes()),
+        ("no data region", 22, &4096u16.to_le_bytes()),
+        ("FAT too small", 22, &[1, 0]),
+        ("FAT[0] without the media byte", reserved * SECTOR_SIZE, &[0x00]),
+    ];
+
+    assert!(Fat16Volume::mount(RamDisk::new(&mut formatted_image())).is_ok());
+    for &(reason, offset, bytes) in cases {
+        let mut image = formatted_image();
+        image[offset..offset + bytes.len()].copy_from_slice(bytes);
+        let result = Fat16Volume::mount(RamDisk::new(&mut image));
+        assert!(matches!(result, Err(Fat16Error::Corrupt)), "accepted: {}", reason);
+    }
+
+    // Pusty dysk (bez BPB) też nie jest FAT16
+    assert!(Fat16Volume::mount(RamDisk::new(&mut blank_image())).is_err());
+}
+
+#[test]
+fn save_spans_several_clusters() {
+    for sectors_per_cluster in [1u8, 4] {
+        let mut image = blank_image();
+        let geometry = Fat16Geometry { sectors_per_cluster, ..geometry_for(IMAGE_SECTORS) };
+        let cluster = sectors_per_cluster as usize * SECTOR_SIZE;
+        let data = pattern(5 * cluster + 100);
+        {
+            let mut volume = Fat16Volume::format(RamDisk::new(&mut image), &geometry).unwrap();
+            volume.save(Dir::ROOT, &name("BIG.BIN"), &data).unwrap();
+
+            let mut buf = vec![0u8; data.len() + cluster];
+            let size = volume.load(Dir::ROOT, &name("BIG.BIN"), &mut buf).unwrap();
+            assert_eq!(&buf[..size], &data[..]);
+
+            // Za mały bufor: plik obcięty do jego długości
+            let mut short = vec![0u8; cluster + 10];
+            let size = volume.load(Dir::ROOT, &name("BIG.BIN"), &mut short).unwrap();
+            assert_eq!(&short[..size], &data[..cluster + 10]);
+        }
+
+        // Łańcuch 6 klastrów zakończony EOC, bez przerw i pętli
+        assert_eq!(used_clusters(&image), 6);
+        let fat = fat(&image);
+        let mut cluster = 2u16;
+        for _ in 0..5 {
+            assert_eq!(fat[cluster as usize], cluster + 1);
+            cluster += 1;
+        }
+        assert!(fat[cluster as usize] >= 0xFFF8);
+    }
+}
+
+#[test]
+fn overwrite_in_place_keeps_one_entry_and_frees_clusters() {
+    let mut image = formatted_image();
+    let file = name("Lista zakupow.txt");
+    let sizes = [10 * SECTOR_SIZE, 600, 3 * SECTOR_SIZE + 1, 0, 2000];
+    for size in sizes {
+        {
+            let mut volume = mount(&mut image);
+            volume.save(Dir::ROOT, &file, &pattern(size)).unwrap();
+            assert_eq!(list(&mut volume, Dir::ROOT), ["Lista zakupow.txt"]);
+
+            let mut buf = vec![0u8; 8192];
+            let loaded = volume.load(Dir::ROOT, &file, &mut buf).unwrap();
+            assert_eq!(&buf[..loaded], &pattern(size)[..]);
+        }
+        // Po skróceniu i wydłużeniu pliku zajęte jest tylko to, czego potrzebuje
+        assert_eq!(used_clusters(&image), size.div_ceil(SECTOR_SIZE), "after saving {} B", size);
+    }
+
+    // Obie kopie FAT zapisane tak samo (write-through)
+    let fat_bytes = get_u16(&image, BPB_FAT_SECTORS) as usize * SECTOR_SIZE;
+    let first = get_u16(&image, BPB_RESERVED_SECTORS) as usize * SECTOR_SIZE;
+    assert_eq!(image[first..first + fat_bytes], image[first + fat_bytes..first + 2 * fat_bytes]);
+}
+
+#[test]
+fn truncate_and_delete_free_clusters() {
+    let mut image = formatted_image();
+    {
+        let mut volume = mount(&mut image);
+        volume.save(Dir::ROOT, &name("A.TXT"), &pattern(4 * SECTOR_SIZE)).unwrap();
+        volume.save(Dir::ROOT, &name("B.TXT"), &pattern(2 * SECTOR_SIZE)).unwrap();
+        volume.truncate(Dir::ROOT, &name("A.TXT"), 700).unwrap();
+    }
+    assert_eq!(used_clusters(&image), 2 + 2);
+    {
+        let mut volume = mount(&mut image);
+        volume.delete(Dir::ROOT, &name("A.TXT")).unwrap();
+        volume.delete(Dir::ROOT, &name("B.TXT")).unwrap();
+        assert!(list(&mut volume, Dir::ROOT).is_empty());
+    }
+    assert_eq!(used_clusters(&image), 0);
+}
+
+#[test]
+fn chain_crosses_fat_sector_boundary() {
+    // Sektor FAT to 256 wpisów: wypełniacz zajmuje klastry 2..=249, więc
+    // plik z 20 klastrów zaczyna się w pierwszym sektorze FAT, a kończy w drugim
+    let mut image = formatted_image();
+    let data = pattern(20 * SECTOR_SIZE);
+    {
+        let mut volume = mount(&mut image);
+        volume.save(Dir::ROOT, &name("FILLER.BIN"), &pattern(248 * SECTOR_SIZE)).unwrap();
+        volume.save(Dir::ROOT, &name("CROSS.BIN"), &data).unwrap();
+    }
+    let fat_entries = fat(&image);
+    assert_eq!(get_u16(root_entry(&mut image, 1), ENTRY_FIRST_CLUSTER), 250);
+    assert_eq!(fat_entries[255], 256);
+    assert!(fat_entries[269] >= 0xFFF8);
+
+    // Zwolnienie wypełniacza i wydłużenie pliku: nowe klastry leżą przed
+    // początkiem łańcucha, a ich ogon znów przechodzi przez granicę sektorów
+    let longer = pattern(300 * SECTOR_SIZE);
+    {
+        let mut volume = mount(&mut image);
+        volume.delete(Dir::ROOT, &name("FILLER.BIN")).unwrap();
+        volume.save(Dir::ROOT, &name("CROSS.BIN"), &longer).unwrap();
+        let mut buf = vec![0u8; longer.len()];
+        assert_eq!(volume.load(Dir::ROOT, &name("CROSS.BIN"), &mut buf).unwrap(), longer.len());
+        assert_eq!(buf, longer);
+    }
+    let fat_entries = fat(&image);
+    assert_eq!(fat_entries[269], 2); // Stary koniec łańcucha wskazuje pierwszy wolny klaster
+    assert_eq!(fat_entries[249], 270);
+    assert_eq!(used_clusters(&image), 300);
+}
+
+#[test]
+fn full_root_directory() {
+    let mut image = formatted_image();
+    {
+        let mut volume = mount(&mut image);
+        // Puste pliki nie zajmują klastrów - liczy się tylko miejsce w katalogu
+        for n in 0..512 {
+            volume.save(Dir::ROOT, &name(&format!("F{}.TXT", n)), b"").unwrap();
+        }
+        assert_eq!(volume.save(Dir::ROOT, &name("ONE.TXT"), b"data"), Err(Fat16Error::DirectoryFull));
+        assert!(matches!(volume.mkdir(Dir::ROOT, &name("DOCS")), Err(Fat16Error::DirectoryFull)));
+        // Istniejący wpis da się nadpisać, bo nie potrzebuje nowego miejsca
+        volume.save(Dir::ROOT, &name("F511.TXT"), b"data").unwrap();
+    }
+    // Nieudane zapisy nie zostawiły zajętych klastrów
+    assert_eq!(used_clusters(&image), 1);
+}
+
+#[test]
+fn volume_out_of_clusters() {
+    // Klastry danych z BPB: za FAT-ami i 32 sektorami katalogu głównego
+    let mut image = formatted_image();
+    let fat_sectors = get_u16(&image, BPB_FAT_SECTORS) as usize;
+    let clusters = IMAGE_SECTORS - get_u16(&image, BPB_RESERVED_SECTORS) as usize - 2 * fat_sectors - 32;
+    {
+        let mut volume = mount(&mut image);
+
+        // O klaster za dużo: nic nie zostaje zaalokowane ani zapisane w katalogu
+        let too_big = pattern((clusters + 1) * SECTOR_SIZE);
+        assert_eq!(volume.save(Dir::ROOT, &name("BIG.BIN"), &too_big), Err(Fat16Error::DiskFull));
+        assert!(list(&mut volume, Dir::ROOT).is_empty());
+
+        // Dokładnie cały dysk bez jednego klastra, potem ostatni klaster
+        let big = pattern((clusters - 1) * SECTOR_SIZE);
+        volume.save(Dir::ROOT, &name("BIG.BIN"), &big).unwrap();
+        assert_eq!(volume.save(Dir::ROOT, &name("TWO.BIN"), &pattern(2 * SECTOR_SIZE)), Err(Fat16Error::DiskFull));
+        volume.save(Dir::ROOT, &name("ONE.BIN"), b"x").unwrap();
+
+        // Pełny dysk: wydłużenie pliku się nie udaje, a jego treść zostaje
+        assert_eq!(volume.save(Dir::ROOT, &name("ONE.BIN"), &pattern(2 * SECTOR_SIZE)), Err(Fat16Error::DiskFull));
+        let mut buf = [0u8; 16];
+        assert_eq!(volume.load(Dir::ROOT, &name("ONE.BIN"), &mut buf), Ok(1));
+        assert_eq!(&buf[..1], b"x");
+        // Katalog główny ma stały rozmiar, więc pusty plik da się jeszcze utworzyć
+        volume.save(Dir::ROOT, &name("EMPTY.TXT"), b"").unwrap();
+    }
+    assert_eq!(used_clusters(&image), clusters);
+}
@@ -0,0 +1,73 @@
+// Całe os.img przez te same funkcje co wiersz poleceń: `build`, potem
+// `--check` i `--repair` na obrazie uszkodzonym ręcznie w pliku
+
+use super::*;
+use crate::fs::fat16::CheckMode;
+use crate::{build, check, Options, BOOT_KERNEL_SECTORS, PARTITION_START};
+use std::path::{Path, PathBuf};
+
+// Osobny katalog w /tmp dla każdego testu (testy idą równolegle)
+fn temp_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("mkimage-{}-{}", std::process::id(), test));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("files")).unwrap();
+    dir
+}
+
+// Boot sector z sygnaturą, jądro na 3 sektory i dwa pliki do skopiowania
+fn build_image(dir: &Path) -> PathBuf {
+    let mut boot = vec![0u8; SECTOR_SIZE];
+    boot[510..].copy_from_slice(&[0x55, 0xAA]);
+    std::fs::write(dir.join("boot.bin"), &boot).unwrap();
+    std::fs::write(dir.join("kernel.bin"), pattern(2 * SECTOR_SIZE + 1)).unwrap();
+    std::fs::write(dir.join("files/README.TXT"), b"Witaj").unwrap();
+    std::fs::write(dir.join("files/Notatki z wykladu.txt"), pattern(3 * SECTOR_SIZE)).unwrap();
+
+    let options = Options {
+        boot: dir.join("boot.bin"),
+        kernel: dir.join("kernel.bin"),
+        files: Some(dir.join("files")),
+        size_mb: 4,
+        output: dir.join("os.img"),
+    };
+    build(&options).expect("build");
+    options.output
+}
+
+#[test]
+fn build_writes_kernel_size_for_the_bootloader() {
+    let dir = temp_dir("build");
+    let image = std::fs::read(build_image(&dir)).unwrap();
+    assert_eq!(get_u16(&image, BOOT_KERNEL_SECTORS), 3);
+    assert_eq!(&image[SECTOR_SIZE..3 * SECTOR_SIZE + 1], &pattern(2 * SECTOR_SIZE + 1)[..]);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn check_and_repair_a_corrupted_image() {
+    let dir = temp_dir("check");
+    let path = build_image(&dir);
+    assert_eq!(check(&path, CheckMode::Report), Ok(()));
+
+    // Zgubiony łańcuch w obu kopiach FAT partycji
+    let mut image = std::fs::read(&path).unwrap();
+    let partition = &mut image[PARTITION_START * SECTOR_SIZE..];
+    set_fat(partition, 500, 501);
+    set_fat(partition, 501, FAT_EOC);
+    std::fs::write(&path, &image).unwrap();
+
+    assert!(check(&path, CheckMode::Report).is_err());
+    assert_eq!(std::fs::read(&path).unwrap(), image, "--check alone must not write");
+    assert_eq!(check(&path, CheckMode::Repair), Ok(()));
+    assert_eq!(check(&path, CheckMode::Report), Ok(()));
+
+    // Naprawa zwolniła tylko zgubione klastry - pliki są nietknięte
+    let mut image = std::fs::read(&path).unwrap();
+    let partition = &mut image[PARTITION_START * SECTOR_SIZE..];
+    assert_eq!(fat(partition)[500], 0);
+    let mut volume = mount(partition);
+    let mut buf = [0u8; 4096];
+    let size = volume.load(Dir::ROOT, &name("notatki z wykladu.txt"), &mut buf).unwrap();
+    assert_eq!(&buf[..size], &pattern(3 * SECTOR_SIZE)[..]);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,246 @@
+// Długie nazwy (VFAT): suma kontrolna, aliasy ~n i odczyt wpisów zapisanych
+// przez inne narzędzia (mkfs.fat -n, mtools, Linux vfat)
+
+use super::*;
+use crate::fs::fat16::{CheckMode, CheckScratch};
+
+const ATTR_LFN: u8 = 0x0F;
+const LFN_OFFSETS: [usize; 13] = [1, 3, 5, 7, 9, 14, 16, 18, 20, 22, 24, 28, 30];
+
+// Suma kontrolna aliasu wprost ze specyfikacji Microsoftu (FATGEN 1.03)
+fn reference_checksum(alias: &[u8]) -> u8 {
+    let mut sum = 0u8;
+    for &c in alias {
+        sum = ((sum & 1) << 7).wrapping_add(sum >> 1).wrapping_add(c);
+    }
+    sum
+}
+
+// Aliasy 8.3 wszystkich plików katalogu głównego, np. "PROJEC~1TXT"
+fn aliases(image: &mut [u8]) -> Vec<String> {
+    let mut aliases = Vec::new();
+    for index in 0..512 {
+        let entry = root_entry(image, index);
+        if entry[0] == 0x00 { break; }
+        if entry[0] == 0xE5 || entry[11] == ATTR_LFN { continue; }
+        aliases.push(String::from_utf8_lossy(&entry[..11]).into_owned());
+    }
+    aliases
+}
+
+// Fragment LFN tak jak zapisują go mkfs.fat/mtools/Linux: znaki UCS-2,
+// po nazwie 0x0000, resztę slotu wypełnia 0xFFFF; typ i klaster równe 0
+fn lfn_slot(name: &str, seq: usize, slots: usize, checksum: u8) -> [u8; 32] {
+    let chars: Vec<u16> = name.encode_utf16().collect();
+    let mut raw = [0u8; 32];
+    raw[0] = seq as u8 | if seq == slots { 0x40 } else { 0 };
+    raw[11] = ATTR_LFN;
+    raw[13] = checksum;
+    for (i, &offset) in LFN_OFFSETS.iter().enumerate() {
+        let pos = (seq - 1) * 13 + i;
+        let c = match pos.cmp(&chars.len()) {
+            std::cmp::Ordering::Less => chars[pos],
+            std::cmp::Ordering::Equal => 0x0000,
+            std::cmp::Ordering::Greater => 0xFFFF,
+        };
+        raw[offset..offset + 2].copy_from_slice(&c.to_le_bytes());
+    }
+    raw
+}
+
+// Pusty plik 8.3 (bez klastrów) albo etykieta woluminu
+fn short_slot(alias: &[u8; 11], attr: u8) -> [u8; 32] {
+    let mut raw = [0u8; 32];
+    raw[..11].copy_from_slice(alias);
+    raw[11] = attr;
+    raw
+}
+
+// Długa nazwa z fragmentami od ostatniego do pierwszego, za nimi alias
+fn long_name_slots(name: &str, alias: &[u8; 11], checksum: u8) -> Vec<[u8; 32]> {
+    let slots = name.encode_utf16().count().div_ceil(13);
+    let mut entries: Vec<_> = (1..=slots).rev().map(|seq| lfn_slot(name, seq, slots, checksum)).collect();
+    entries.push(short_slot(alias, 0x20));
+    entries
+}
+
+#[test]
+fn checksum_matches_the_specification() {
+    let mut image = formatted_image();
+    mount(&mut image).save(Dir::ROOT, &name("Notatki z wykladu.txt"), b"x").unwrap();
+
+    // 21 znaków = 2 fragmenty LFN (wpisy 0 i 1), alias we wpisie 2
+    assert_eq!(aliases(&mut image), ["NOTATK~1TXT"]);
+    let alias: [u8; 11] = root_entry(&mut image, 2)[..11].try_into().unwrap();
+    assert_eq!(reference_checksum(&alias), 14);
+    assert_eq!(root_entry(&mut image, 0)[0], 0x42); // Ostatni fragment pierwszy na dysku
+    assert_eq!(root_entry(&mut image, 1)[0], 0x01);
+    for index in 0..2 {
+        let slot = root_entry(&mut image, index);
+        assert_eq!(slot[11], ATTR_LFN);
+        assert_eq!(slot[13], 14, "checksum in slot {}", index);
+        assert_eq!(get_u16(slot, 26), 0);
+    }
+    // Zapisane fragmenty to dokładnie to, co zapisałby mkfs.fat/mtools
+    let expected = long_name_slots("Notatki z wykladu.txt", &alias, 14);
+    for (index, slot) in expected.iter().take(2).enumerate() {
+        assert_eq!(root_entry(&mut image, index), &slot[..]);
+    }
+}
+
+#[test]
+fn aliases_get_numeric_tails() {
+    let mut image = formatted_image();
+    {
+        let mut volume = mount(&mut image);
+        for n in 1..=12 {
+            volume.save(Dir::ROOT, &name(&format!("Project file {}.txt", n)), b"x").unwrap();
+        }
+    }
+    let aliases = aliases(&mut image);
+    assert_eq!(aliases.len(), 12);
+    assert_eq!(aliases[0], "PROJEC~1TXT");
+    assert_eq!(aliases[8], "PROJEC~9TXT");
+    // Dwucyfrowy numer skraca rdzeń: PROJE~10, PROJE~12
+    assert_eq!(aliases[9], "PROJE~10TXT");
+    assert_eq!(aliases[11], "PROJE~12TXT");
+}
+
+#[test]
+fn aliases_avoid_existing_names() {
+    let mut image = formatted_image();
+    {
+        let mut volume = mount(&mut image);
+        // Plik 8.3 o nazwie, którą dostałby pierwszy alias
+        volume.save(Dir::ROOT, &name("PROJEC~1.TXT"), b"x").unwrap();
+        volume.save(Dir::ROOT, &name("Project plan.txt"), b"x").unwrap();
+        // Kropki i spacje znikają z rdzenia, rozszerzenie to 3 znaki za ostatnią kropką
+        volume.save(Dir::ROOT, &name("my.long name.text"), b"x").unwrap();
+        // Nazwa różna tylko wielkością liter nie dostaje numeru
+        volume.save(Dir::ROOT, &name("readme.txt"), b"x").unwrap();
+        // Znaki spoza ASCII (nazwy z hosta) w aliasie jako `_`
+        let polish: Vec<u16> = "Zażółć.txt".encode_utf16().collect();
+        volume.save(Dir::ROOT, &FileName::from_ucs2(&polish).unwrap(), b"x").unwrap();
+    }
+    assert_eq!(aliases(&mut image), ["PROJEC~1TXT", "PROJEC~2TXT", "MYLONG~1TEX", "README  TXT", "ZA____~1TXT"]);
+}
+
+#[test]
+fn reads_long_names_written_by_other_tools() {
+    let mut image = formatted_image();
+    let mut entries = vec![short_slot(b"MYDISK     ", 0x08)]; // mkfs.fat -n MYDISK
+    entries.extend(long_name_slots("Dokument testowy.txt", b"DOKUME~1TXT", 160));
+    // Dokładnie 13 znaków: jeden fragment bez terminatora i wypełnienia
+    let alias = *b"ABCDEF~1MD ";
+    entries.extend(long_name_slots("abcdefghij.md", &alias, reference_checksum(&alias)));
+    // Fragment z cudzą sumą kontrolną (np. alias zmieniony przez DOS) jest pomijany
+    entries.extend(long_name_slots("Stara nazwa.txt", b"NOWA    TXT", 0x55));
+    for (index, entry) in entries.iter().enumerate() {
+        root_entry(&mut image, index).copy_from_slice(entry);
+    }
+
+    {
+        let mut volume = mount(&mut image);
+        assert_eq!(list(&mut volume, Dir::ROOT), ["MYDISK", "Dokument testowy.txt", "abcdefghij.md", "NOWA.TXT"]);
+        let mut buf = [0u8; 16];
+        for path in ["Dokument testowy.txt", "DOKUMENT TESTOWY.TXT", "DOKUME~1.TXT", "abcdefghij.md", "NOWA.TXT"] {
+            assert_eq!(volume.load(Dir::ROOT, &name(path), &mut buf), Ok(0), "{}", path);
+        }
+        assert!(volume.load(Dir::ROOT, &name("Stara nazwa.txt"), &mut buf).is_err());
+
+        // Nowy plik nie zajmuje aliasu z cudzego wpisu
+        volume.save(Dir::ROOT, &name("Dokument nowy.txt"), b"x").unwrap();
+    }
+    assert!(aliases(&mut image).contains(&"DOKUME~2TXT".to_string()));
+    let mut scratch = CheckScratch::EMPTY;
+    let report = mount(&mut image).check(CheckMode::Report, &mut scratch).unwrap();
+    assert!(report.is_clean(), "{}", report);
+}
+
+#[test]
+fn repair_renames_long_names_that_differ_only_in_case() {
+    let mut image = formatted_image();
+    // FSCK.TXT już jest - naprawa musi wybrać inny alias
+    let mut entries = vec![short_slot(b"FSCK    TXT", 0x20)];
+    let first = *b"NOTATK~1TXT";
+    let second = *b"NOTATK~2TXT";
+    entries.extend(long_name_slots("Notatki.txt", &first, reference_checksum(&first)));
+    entries.extend(long_name_slots("NOTATKI.txt", &second, reference_checksum(&second)));
+    for (index, entry) in entries.iter().enumerate() {
+        root_entry(&mut image, index).copy_from_slice(entry);
+    }
+
+    let mut scratch = CheckScratch::EMPTY;
+    let report = mount(&mut image).check(CheckMode::Report, &mut scratch).unwrap();
+    assert_eq!((report.duplicate_names, report.errors()), (1, 1));
+    let report = mount(&mut image).check(CheckMode::Repair, &mut scratch).unwrap();
+    assert_eq!(report.duplicate_names, 1);
+    let report = mount(&mut image).check(CheckMode::Report, &mut scratch).unwrap();
+    assert!(report.is_clean(), "after repair: {}", report);
+
+    // Fragment LFN drugiego wpisu zwolniony, a nie osierocony
+    assert_eq!(root_entry(&mut image, 3)[0], 0xE5);
+    assert_eq!(aliases(&mut image), ["FSCK    TXT", "NOTATK~1TXT", "FSCK~1  TXT"]);
+    let mut volume = mount(&mut image);
+    assert_eq!(list(&mut volume, Dir::ROOT), ["FSCK.TXT", "Notatki.txt", "FSCK~1.TXT"]);
+}
+
+#[test]
+fn file_name_rejects_what_fat_cannot_store() {
+    assert!(FileName::parse(b".profile").is_ok()); // Kropka na początku jest dozwolona
+    assert!(FileName::parse(&[b'x'; 255]).is_ok());
+    let invalid: [&[u8]; 10] = [b"", b".", b"..", b"notes.", b"notes ", &[b'x'; 256], b"a\tb", b"a:b", b"a*b", b"a|b"];
+    for text in invalid {
+        assert!(FileName::parse(text).is_err(), "{:?}", String::from_utf8_lossy(text));
+    }
+    // Tylko ASCII z jądra; znaki spoza ASCII przychodzą jako UCS-2
+    assert!(FileName::parse("żółw.txt".as_bytes()).is_err());
+}
+
+#[test]
+fn only_upper_case_8_3_names_skip_lfn() {
+    let mut image = formatted_image();
+    {
+        let mut volume = mount(&mut image);
+        for text in ["README.TXT", "readme.md", "Makefile", "a.b.c", "...x.txt", ".profile", "verylongname.text"] {
+            volume.save(Dir::ROOT, &name(text), b"x").unwrap();
+        }
+        // Nazwy różne tylko wielkością liter to ten sam plik
+        volume.save(Dir::ROOT, &name("readme.txt"), b"y").unwrap();
+        volume.save(Dir::ROOT, &name("MAKEFILE"), b"y").unwrap();
+        assert_eq!(
+            list(&mut volume, Dir::ROOT),
+            ["README.TXT", "readme.md", "Makefile", "a.b.c", "...x.txt", ".profile", "verylongname.text"]
+        );
+    }
+    // Tylko README.TXT jest wpisem bez fragmentów LFN
+    assert_eq!(root_entry(&mut image, 0)[11], 0x20);
+    assert_eq!(root_entry(&mut image, 1)[11], ATTR_LFN);
+    assert_eq!(
+        aliases(&mut image),
+        ["README  TXT", "README  MD ", "MAKEFILE   ", "AB~1    C  ", "X~1     TXT", "PROFIL~1   ", "VERYLO~1TEX"]
+    );
+}
+
+#[test]
+fn alias_tails_past_nine() {
+    let mut image = formatted_image();
+    {
+        let mut volume = mount(&mut image);
+        // Krótki rdzeń zostaje cały, długi ustępuje numerowi
+        for n in 1..=11 {
+            volume.save(Dir::ROOT, &name(&format!("a b {}.txt", "x".repeat(n))), b"").unwrap();
+        }
+        for n in 1..=100 {
+            volume.save(Dir::ROOT, &name(&format!("Raport {}.doc", n)), b"").unwrap();
+        }
+    }
+    let aliases = aliases(&mut image);
+    assert_eq!(aliases.len(), 111);
+    assert_eq!(aliases[0], "ABX~1   TXT");
+    assert_eq!(aliases[1], "ABXX~1  TXT");
+    assert_eq!(aliases[11], "RAPORT~1DOC");
+    assert_eq!(aliases[19], "RAPORT~9DOC");
+    assert_eq!(aliases[20], "RAPOR~10DOC");
+    assert_eq!(aliases[110], "RAPO~100DOC");
+}
@@ -0,0 +1,108 @@
+// =============================================================================
+// TESTY - sterownik FAT16 jądra na obrazie w pamięci hosta
+// =============================================================================
+// Te same pliki co w jądrze (`#[path]` w main.rs), tylko RamDisk leży w
+// `Vec<u8>`. Surowe bajty obrazu czytamy bezpośrednio, żeby sprawdzić, co
+// naprawdę trafiło na dysk (FAT, wpisy katalogu).
+// =============================================================================
+
+mod check;
+mod fat16;
+mod image;
+mod lfn;
+mod names;
+
+use crate::fs::block::{BlockDevice, RamDisk};
+use crate::fs::fat16::{Dir, Fat16Volume, FileName};
+use crate::{geometry_for, SECTOR_SIZE};
+
+// 4 MB jak RAMDISK jądra: klaster = sektor, 512 wpisów w katalogu głównym
+pub const IMAGE_SECTORS: usize = 8192;
+
+// Offsety pól BPB (jak w volume.rs)
+pub const BPB_BYTES_PER_SECTOR: usize = 11;
+pub const BPB_RESERVED_SECTORS: usize = 14;
+pub const BPB_FAT_COUNT: usize = 16;
+pub const BPB_FAT_SECTORS: usize = 22;
+
+// Offsety pól wpisu katalogu
+pub const ENTRY_FIRST_CLUSTER: usize = 26;
+pub const ENTRY_FILE_SIZE: usize = 28;
+
+pub const FAT_EOC: u16 = 0xFFFF;
+
+pub fn blank_image() -> Vec<u8> {
+    vec![0; IMAGE_SECTORS * SECTOR_SIZE]
+}
+
+pub fn formatted_image() -> Vec<u8> {
+    let mut image = blank_image();
+    Fat16Volume::format(RamDisk::new(&mut image), &geometry_for(IMAGE_SECTORS)).expect("format");
+    image
+}
+
+pub fn mount(image: &mut [u8]) -> Fat16Volume<RamDisk<'_>> {
+    Fat16Volume::mount(RamDisk::new(image)).expect("mount")
+}
+
+pub fn name(text: &str) -> FileName {
+    FileName::parse(text.as_bytes()).expect("valid name")
+}
+
+// Treść bez powtórzeń co sektor - przesunięty klaster nie przejdzie porównania
+pub fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 7 + i / 509) as u8).collect()
+}
+
+pub fn get_u16(image: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([image[offset], image[offset + 1]])
+}
+
+// Pierwsza kopia FAT z surowego obrazu (od wpisu 0)
+pub fn fat(image: &[u8]) -> Vec<u16> {
+    let bps = get_u16(image, BPB_BYTES_PER_SECTOR) as usize;
+    let start = get_u16(image, BPB_RESERVED_SECTORS) as usize * bps;
+    let len = get_u16(image, BPB_FAT_SECTORS) as usize * bps;
+    image[start..start + len].chunks(2).map(|e| u16::from_le_bytes([e[0], e[1]])).collect()
+}
+
+// Zapis wpisu FAT w wybranej kopii (0 = pierwsza)
+pub fn set_fat_copy(image: &mut [u8], copy: usize, cluster: u16, value: u16) {
+    let bps = get_u16(image, BPB_BYTES_PER_SECTOR) as usize;
+    let fat_bytes = get_u16(image, BPB_FAT_SECTORS) as usize * bps;
+    let offset = get_u16(image, BPB_RESERVED_SECTORS) as usize * bps + copy * fat_bytes + cluster as usize * 2;
+    image[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+}
+
+// Zapis we wszystkich kopiach - uszkodzenie łańcucha, nie niezgodność kopii
+pub fn set_fat(image: &mut [u8], cluster: u16, value: u16) {
+    for copy in 0..image[BPB_FAT_COUNT] as usize {
+        set_fat_copy(image, copy, cluster, value);
+    }
+}
+
+// Surowy 32-bajtowy wpis katalogu głównego nr `index`
+pub fn root_entry(image: &mut [u8], index: usize) -> &mut [u8] {
+    let bps = get_u16(image, BPB_BYTES_PER_SECTOR) as usize;
+    let root_sector = get_u16(image, BPB_RESERVED_SECTORS) as usize
+        + image[BPB_FAT_COUNT] as usize * get_u16(image, BPB_FAT_SECTORS) as usize;
+    let offset = root_sector * bps + index * 32;
+    &mut image[offset..offset + 32]
+}
+
+// Zajęte klastry danych - wszystko poza wpisami 0 i 1
+pub fn used_clusters(image: &[u8]) -> usize {
+    fat(image)[2..].iter().filter(|&&entry| entry != 0).count()
+}
+
+// Nazwy wszystkich wpisów katalogu w kolejności na dysku
+pub fn list<D: BlockDevice>(volume: &mut Fat16Volume<D>, dir: Dir) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut index = 0;
+    while let Some(item) = volume.next_item(dir, index).expect("read directory") {
+        let (text, len) = item.name.text();
+        names.push(String::from_utf8_lossy(&text[..len]).into_owned());
+        index = item.index + 1;
+    }
+    names
+}
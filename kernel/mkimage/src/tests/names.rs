@@ -0,0 +1,80 @@
+// Nazwy 8.3: parsowanie nazw wpisanych przez użytkownika i pól z dysku
+
+use crate::fs::fat16::{Fat16Error, ShortName};
+
+fn parse(text: &str) -> Result<String, Fat16Error> {
+    ShortName::parse(text.as_bytes()).map(|short| short.to_string())
+}
+
+#[test]
+fn parse_accepts_8_3_names() {
+    let cases = [
+        ("README.TXT", "README.TXT"),
+        ("readme.txt", "README.TXT"), // Wielkie litery jak na dysku
+        ("Makefile", "MAKEFILE"),     // Bez kropki - bez rozszerzenia
+        ("ABCDEFGH.XYZ", "ABCDEFGH.XYZ"),
+        ("A.B", "A.B"),
+        ("PROJEC~1.TXT", "PROJEC~1.TXT"),
+        ("{X}_(Y).@!#", "{X}_(Y).@!#"),
+        ("2026-10.LOG", "2026-10.LOG"),
+        ("NOTES.", "NOTES"),          // Pusta część za kropką jak jej brak
+    ];
+    for (text, expected) in cases {
+        assert_eq!(parse(text).as_deref(), Ok(expected), "{}", text);
+    }
+}
+
+#[test]
+fn parse_rejects_what_does_not_fit_8_3() {
+    let cases = [
+        "",
+        ".",
+        "..",
+        ".PROFILE",         // Kropka na początku - pusta nazwa
+        "LONGNAME1.TXT",    // 9 znaków nazwy
+        "NOTES.TEXT",       // 4 znaki rozszerzenia
+        "A.B.C",            // Druga kropka
+        "MY FILE.TXT",      // Spacja w środku
+        "FILE .TXT",        // Spacja przed kropką
+        "FILE.TX ",         // Spacja na końcu
+        " FILE.TXT",        // Spacja na początku
+        "A+B.TXT",
+        "A,B.TXT",
+        "A*.TXT",
+        "A?.TXT",
+        "A\"B.TXT",
+        "DIR/FILE",
+        "A:B",
+        "[X].TXT",
+        "X.;",
+    ];
+    for text in cases {
+        assert_eq!(parse(text), Err(Fat16Error::InvalidName), "{:?}", text);
+    }
+}
+
+#[test]
+fn parse_ignores_case() {
+    let upper = ShortName::parse(b"NOTATKI.TXT").unwrap();
+    for text in ["notatki.txt", "Notatki.Txt", "nOtAtKi.TxT"] {
+        assert_eq!(ShortName::parse(text.as_bytes()), Ok(upper), "{}", text);
+    }
+    assert_ne!(ShortName::parse(b"NOTATKI.TX").unwrap(), upper);
+}
+
+#[test]
+fn fields_from_disk_are_checked() {
+    assert!(ShortName::new(b"README  ", b"TXT").is_ok());
+    // Bajty spoza ASCII (strony kodowe DOS) są dozwolone
+    assert!(ShortName::new(b"\x80\xA5BC    ", b"   ").is_ok());
+    let invalid: [(&[u8; 8], &[u8; 3]); 5] = [
+        (b"        ", b"TXT"), // Pusta nazwa
+        (b"\xE5ABC    ", b"TXT"), // Pierwszy bajt = znacznik usunięcia
+        (b"readme  ", b"TXT"), // Małe litery
+        (b"READ ME ", b"TXT"), // Spacja w środku
+        (b"README  ", b"T T"),
+    ];
+    for (name, ext) in invalid {
+        assert_eq!(ShortName::new(name, ext), Err(Fat16Error::InvalidName), "{:?}", name);
+    }
+}
@@ -85,6 +85,7 @@ long_mode_start:
     
     ; Set up stack for 64-bit mode
     mov rsp, stack_top
+    xor ebp, ebp                 ; Koniec łańcucha ramek (backtrace przy panic)
     
     ; Call Rust kernel
     call kernel_main
@@ -0,0 +1,171 @@
+// =============================================================================
+// APLIKACJE (ekrany) i ich stos
+// =============================================================================
+// Każdy ekran (menu główne, Math, Unicode, Mini-Commander, edytor, pasek
+// INPUT, okno komunikatu) implementuje `App`. Router trzyma stos identyfikatorów `AppId`:
+// klawisze trafiają tylko do aplikacji na szczycie, a ta zwraca `Action`
+// mówiącą, czy otworzyć nowy ekran, czy wrócić do poprzedniego.
+//
+// Nowy ekran = nowa struktura + wariant `AppId` + jedna linia w `with_app`.
+// =============================================================================
+
+use core::cell::UnsafeCell;
+use crate::editor::{self, TextEditor};
+use crate::keyboard::{self, KeyEvent};
+use crate::mc::MiniCommander;
+use crate::menu::{InputPrompt, MainMenu, MathMenu, MessageBox, UnicodeMenu};
+
+const MAX_DEPTH: usize = 8;
+
+const KEY_F5: u8 = 0x3F;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppId {
+    MainMenu,
+    Math,
+    Unicode,
+    MiniCommander,
+    Editor,
+    Input,
+    Message,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    None,
+    Push(AppId),    // Otwórz ekran nad bieżącym
+    Pop,            // Zamknij bieżący ekran i wróć do poprzedniego
+    Replace(AppId), // Zamknij bieżący i otwórz inny na jego miejscu
+    Dismiss,        // Zamknij nakładkę bez przerysowania ekranu pod spodem
+}
+
+pub trait App {
+    // Aplikacja trafia na szczyt stosu: po otwarciu lub po powrocie z ekranu potomnego
+    fn on_enter(&mut self) {
+        self.draw();
+    }
+
+    // Aplikacja jest zdejmowana ze stosu
+    fn on_exit(&mut self) {}
+
+    fn draw(&self);
+
+    fn on_key(&mut self, key: KeyEvent) -> Action;
+}
+
+struct Apps {
+    main: MainMenu,
+    math: MathMenu,
+    unicode: UnicodeMenu,
+    mc: MiniCommander,
+    input: InputPrompt,
+    message: MessageBox,
+}
+
+struct AppsCell(UnsafeCell<Apps>);
+unsafe impl Sync for AppsCell {}
+
+static APPS: AppsCell = AppsCell(UnsafeCell::new(Apps {
+    main: MainMenu::new(),
+    math: MathMenu,
+    unicode: UnicodeMenu,
+    mc: MiniCommander::new(),
+    input: InputPrompt,
+    message: MessageBox,
+}));
+
+// Router jest jedynym użytkownikiem APPS - wywołania nie są zagnieżdżane
+fn with_app<R>(id: AppId, f: impl FnOnce(&mut dyn App) -> R) -> R {
+    let apps = unsafe { &mut *APPS.0.get() };
+    match id {
+        AppId::MainMenu => f(&mut apps.main),
+        AppId::Math => f(&mut apps.math),
+        AppId::Unicode => f(&mut apps.unicode),
+        AppId::MiniCommander => f(&mut apps.mc),
+        AppId::Input => f(&mut apps.input),
+        AppId::Message => f(&mut apps.message),
+        AppId::Editor => editor::with_editor(|e: &mut TextEditor| f(e)),
+    }
+}
+
+struct AppStack {
+    ids: [AppId; MAX_DEPTH],
+    depth: usize,
+}
+
+impl AppStack {
+    fn top(&self) -> AppId {
+        self.ids[self.depth - 1]
+    }
+
+    fn push(&mut self, id: AppId) {
+        if self.depth >= MAX_DEPTH {
+            log_warn!("app stack full, {:?} not opened", id);
+            return;
+        }
+        self.ids[self.depth] = id;
+        self.depth += 1;
+        with_app(id, |app| app.on_enter());
+    }
+
+    fn pop(&mut self) {
+        // Menu główne zostaje zawsze na dnie stosu
+        if self.depth <= 1 { return; }
+        with_app(self.top(), |app| app.on_exit());
+        self.depth -= 1;
+        with_app(self.top(), |app| app.on_enter());
+    }
+
+    fn dismiss(&mut self) {
+        if self.depth <= 1 { return; }
+        with_app(self.top(), |app| app.on_exit());
+        self.depth -= 1;
+    }
+
+    fn replace(&mut self, id: AppId) {
+        with_app(self.top(), |app| app.on_exit());
+        self.ids[self.depth - 1] = id;
+        with_app(id, |app| app.on_enter());
+    }
+
+    fn apply(&mut self, action: Action) {
+        match action {
+            Action::None => {}
+            Action::Push(id) => self.push(id),
+            Action::Pop => self.pop(),
+            Action::Replace(id) => self.replace(id),
+            Action::Dismiss => self.dismiss(),
+        }
+    }
+}
+
+// Debug: ostatni scancode (również break code) w prawym górnym rogu
+fn show_scancode(scancode: u8) {
+    let hex = |n: u8| if n < 10 { b'0' + n } else { b'A' + n - 10 };
+    vga_write!(74, 0, b'S', 0x0F);
+    vga_write!(75, 0, b'C', 0x0F);
+    vga_write!(77, 0, hex(scancode >> 4), 0x0E);
+    vga_write!(78, 0, hex(scancode & 0x0F), 0x0E);
+}
+
+// Pętla główna UI - nigdy nie wraca
+pub fn run() -> ! {
+    let mut stack = AppStack { ids: [AppId::MainMenu; MAX_DEPTH], depth: 0 };
+    stack.push(AppId::MainMenu);
+
+    loop {
+        let key = keyboard::wait_event();
+        log_trace!("key {:?} -> {:?}", key, stack.top());
+
+        // --- Klawisze globalne, niezależne od ekranu ---
+        if !key.repeat {
+            show_scancode(key.raw());
+        }
+        if key.pressed && key.scancode == KEY_F5 && !keyboard::shift_down() { // F5 - Reboot (Shift+F5 to kopiowanie w MC)
+            unsafe { core::arch::asm!("out 0x64, al", in("al") 0xFEu8); } // Fast reset
+        }
+
+        let action = with_app(stack.top(), |app| app.on_key(key));
+        stack.apply(action);
+    }
+}
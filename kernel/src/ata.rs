@@ -0,0 +1,270 @@
+// =============================================================================
+// ATA PIO - dyski IDE (LBA28) na kanale primary i secondary
+// =============================================================================
+// Transfer programowy (PIO) z odpytywaniem statusu, bez DMA i bez przerwań
+// (nIEN ustawione), więc sterownik działa też przed włączeniem IRQ.
+// W QEMU: `-drive file=os.img` to primary master, kolejny `-drive` to
+// primary slave.
+// =============================================================================
+
+use crate::fs::block::{BlockDevice, BlockError};
+use crate::io::{inb, inw, outb, outw};
+
+pub const SECTOR_SIZE: usize = 512;
+const WORDS_PER_SECTOR: usize = SECTOR_SIZE / 2;
+const LBA28_LIMIT: u64 = 1 << 28;
+const MAX_SECTORS_PER_COMMAND: usize = 256; // Licznik 0 = 256 sektorów
+
+// Rejestry kanału (offset od portu bazowego)
+const REG_DATA: u16 = 0;
+const REG_ERROR: u16 = 1;
+const REG_SECTOR_COUNT: u16 = 2;
+const REG_LBA_LOW: u16 = 3;
+const REG_LBA_MID: u16 = 4;
+const REG_LBA_HIGH: u16 = 5;
+const REG_DRIVE: u16 = 6;
+const REG_STATUS: u16 = 7;  // Odczyt
+const REG_COMMAND: u16 = 7; // Zapis
+
+// Bity statusu
+const STATUS_ERR: u8 = 0x01;
+const STATUS_DRQ: u8 = 0x08;
+const STATUS_DF: u8 = 0x20;
+const STATUS_BSY: u8 = 0x80;
+
+const CMD_READ_SECTORS: u8 = 0x20;
+const CMD_WRITE_SECTORS: u8 = 0x30;
+const CMD_CACHE_FLUSH: u8 = 0xE7;
+const CMD_IDENTIFY: u8 = 0xEC;
+
+const CONTROL_NIEN: u8 = 0x02; // Wyłącza IRQ14/15 z dysku - odpytujemy status
+
+// Ile odczytów statusu czekamy na dysk (~1 s na sprzęcie, 1 odczyt ~ 1 µs)
+const POLL_LIMIT: u32 = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    Primary,
+    Secondary,
+}
+
+impl Channel {
+    fn io_base(self) -> u16 {
+        match self {
+            Channel::Primary => 0x1F0,
+            Channel::Secondary => 0x170,
+        }
+    }
+
+    fn control(self) -> u16 {
+        match self {
+            Channel::Primary => 0x3F6,
+            Channel::Secondary => 0x376,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct AtaDrive {
+    pub channel: Channel,
+    pub slave: bool,
+    sectors: u64,      // Liczba sektorów adresowalnych LBA28
+    model: [u8; 40],   // Nazwa modelu z IDENTIFY (dopełniona spacjami)
+}
+
+impl AtaDrive {
+    fn reg(&self, offset: u16) -> u16 {
+        self.channel.io_base() + offset
+    }
+
+    fn status(&self) -> u8 {
+        unsafe { inb(self.reg(REG_STATUS)) }
+    }
+
+    // Odczyt rejestru Alternate Status nie kasuje przerwania; cztery odczyty
+    // dają wymagane 400 ns po wyborze dysku lub wysłaniu komendy
+    fn delay_400ns(&self) {
+        for _ in 0..4 {
+            unsafe { inb(self.channel.control()); }
+        }
+    }
+
+    fn select(&self, lba_top: u8) {
+        // 0xE0 = LBA + bity zawsze ustawione, bit 4 wybiera slave
+        let drive = 0xE0 | if self.slave { 0x10 } else { 0 } | (lba_top & 0x0F);
+        unsafe {
+            outb(self.channel.control(), CONTROL_NIEN);
+            outb(self.reg(REG_DRIVE), drive);
+        }
+        self.delay_400ns();
+    }
+
+    fn wait_not_busy(&self) -> Result<u8, BlockError> {
+        for _ in 0..POLL_LIMIT {
+            let status = self.status();
+            if status & STATUS_BSY == 0 { return Ok(status); }
+        }
+        Err(BlockError::Timeout)
+    }
+
+    // Czeka aż dysk będzie gotowy do transferu sektora (DRQ)
+    fn wait_data(&self) -> Result<(), BlockError> {
+        for _ in 0..POLL_LIMIT {
+            let status = self.status();
+            if status & STATUS_BSY != 0 { continue; }
+            if status & (STATUS_ERR | STATUS_DF) != 0 {
+                let error = unsafe { inb(self.reg(REG_ERROR)) };
+                log_error!("ATA {:?} error, status {:#04x} error {:#04x}", self.channel, status, error);
+                return Err(BlockError::Device);
+            }
+            if status & STATUS_DRQ != 0 { return Ok(()); }
+        }
+        Err(BlockError::Timeout)
+    }
+
+    // Czeka na koniec komendy. Błąd ostatniego sektora (ERR/DF) dysk zgłasza
+    // dopiero po zdjęciu BSY - następna komenda skasowałaby ten status.
+    fn wait_done(&self) -> Result<(), BlockError> {
+        let status = self.wait_not_busy()?;
+        if status & (STATUS_ERR | STATUS_DF) != 0 {
+            let error = unsafe { inb(self.reg(REG_ERROR)) };
+            log_error!("ATA {:?} error, status {:#04x} error {:#04x}", self.channel, status, error);
+            return Err(BlockError::Device);
+        }
+        Ok(())
+    }
+
+    // Wykrywa dysk ATA na danej pozycji. None gdy nic nie jest podłączone
+    // albo urządzenie nie jest dyskiem ATA (np. CD-ROM ATAPI).
+    pub fn identify(channel: Channel, slave: bool) -> Option<AtaDrive> {
+        let mut drive = AtaDrive { channel, slave, sectors: 0, model: [b' '; 40] };
+
+        // 0xFF = pływająca szyna, brak kontrolera na kanale
+        if drive.status() == 0xFF { return None; }
+        drive.select(0);
+        unsafe {
+            outb(drive.reg(REG_SECTOR_COUNT), 0);
+            outb(drive.reg(REG_LBA_LOW), 0);
+            outb(drive.reg(REG_LBA_MID), 0);
+            outb(drive.reg(REG_LBA_HIGH), 0);
+            outb(drive.reg(REG_COMMAND), CMD_IDENTIFY);
+        }
+        drive.delay_400ns();
+        if drive.status() == 0 { return None; } // Brak dysku
+
+        drive.wait_not_busy().ok()?;
+        // ATAPI i SATA w trybie natywnym wystawiają sygnaturę w LBA mid/high
+        let signature = unsafe { (inb(drive.reg(REG_LBA_MID)), inb(drive.reg(REG_LBA_HIGH))) };
+        if signature != (0, 0) { return None; }
+        drive.wait_data().ok()?;
+
+        let mut words = [0u16; WORDS_PER_SECTOR];
+        for word in words.iter_mut() {
+            *word = unsafe { inw(drive.reg(REG_DATA)) };
+        }
+
+        // Słowa 60-61: liczba sektorów LBA28 (0 = dysk bez LBA)
+        drive.sectors = (words[60] as u64 | (words[61] as u64) << 16).min(LBA28_LIMIT);
+        if drive.sectors == 0 { return None; }
+        // Słowa 27-46: model, dwa znaki na słowo w odwróconej kolejności
+        for (i, word) in words[27..47].iter().enumerate() {
+            drive.model[i * 2] = (word >> 8) as u8;
+            drive.model[i * 2 + 1] = *word as u8;
+        }
+        Some(drive)
+    }
+
+    pub fn model(&self) -> &str {
+        core::str::from_utf8(&self.model).unwrap_or("?").trim_end()
+    }
+
+    // Ustawia LBA i liczbę sektorów, po czym wysyła komendę
+    fn command(&self, command: u8, lba: u64, count: usize) -> Result<(), BlockError> {
+        self.select((lba >> 24) as u8);
+        self.wait_not_busy()?;
+        unsafe {
+            outb(self.reg(REG_SECTOR_COUNT), count as u8); // 256 -> 0
+            outb(self.reg(REG_LBA_LOW), lba as u8);
+            outb(self.reg(REG_LBA_MID), (lba >> 8) as u8);
+            outb(self.reg(REG_LBA_HIGH), (lba >> 16) as u8);
+            outb(self.reg(REG_COMMAND), command);
+        }
+        self.delay_400ns();
+        Ok(())
+    }
+}
+
+impl BlockDevice for AtaDrive {
+    fn block_size(&self) -> usize {
+        SECTOR_SIZE
+    }
+
+    fn block_count(&self) -> u64 {
+        self.sectors
+    }
+
+    fn read_blocks(&mut self, lba: u64, buf: &mut [u8]) -> Result<(), BlockError> {
+        self.check_request(lba, buf.len())?;
+        let mut lba = lba;
+        for chunk in buf.chunks_mut(MAX_SECTORS_PER_COMMAND * SECTOR_SIZE) {
+            self.command(CMD_READ_SECTORS, lba, chunk.len() / SECTOR_SIZE)?;
+            for sector in chunk.chunks_mut(SECTOR_SIZE) {
+                self.wait_data()?;
+                for pair in sector.chunks_mut(2) {
+                    let word = unsafe { inw(self.reg(REG_DATA)) };
+                    pair.copy_from_slice(&word.to_le_bytes());
+                }
+                self.delay_400ns();
+            }
+            lba += (chunk.len() / SECTOR_SIZE) as u64;
+        }
+        Ok(())
+    }
+
+    fn write_blocks(&mut self, lba: u64, buf: &[u8]) -> Result<(), BlockError> {
+        self.check_request(lba, buf.len())?;
+        let mut lba = lba;
+        for chunk in buf.chunks(MAX_SECTORS_PER_COMMAND * SECTOR_SIZE) {
+            self.command(CMD_WRITE_SECTORS, lba, chunk.len() / SECTOR_SIZE)?;
+            for sector in chunk.chunks(SECTOR_SIZE) {
+                self.wait_data()?;
+                for pair in sector.chunks(2) {
+                    unsafe { outw(self.reg(REG_DATA), u16::from_le_bytes([pair[0], pair[1]])); }
+                }
+                self.delay_400ns();
+            }
+            self.wait_done()?;
+            lba += (chunk.len() / SECTOR_SIZE) as u64;
+        }
+        Ok(())
+    }
+
+    // Zapis jest trwały dopiero po opróżnieniu cache dysku
+    fn flush(&mut self) -> Result<(), BlockError> {
+        self.select(0);
+        unsafe { outb(self.reg(REG_COMMAND), CMD_CACHE_FLUSH); }
+        self.delay_400ns();
+        self.wait_done()
+    }
+}
+
+// Wszystkie wykryte dyski: primary master/slave, secondary master/slave
+pub fn probe() -> [Option<AtaDrive>; 4] {
+    let mut drives = [None; 4];
+    let positions = [
+        (Channel::Primary, false),
+        (Channel::Primary, true),
+        (Channel::Secondary, false),
+        (Channel::Secondary, true),
+    ];
+    for (slot, (channel, slave)) in drives.iter_mut().zip(positions) {
+        *slot = AtaDrive::identify(channel, slave);
+        if let Some(drive) = slot {
+            log_info!(
+                "ATA {:?} {}: {} sectors, \"{}\"",
+                channel, if slave { "slave" } else { "master" }, drive.sectors, drive.model()
+            );
+        }
+    }
+    drives
+}
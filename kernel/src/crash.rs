@@ -0,0 +1,187 @@
+// =============================================================================
+// EKRAN AWARII ("blue screen") - wyjątki CPU i panic
+// =============================================================================
+// Raport trafia jednocześnie na VGA i na COM1, więc da się go odczytać także
+// z terminala QEMU (-serial stdio), gdy obraz jest niedostępny.
+//
+// Backtrace opiera się na łańcuchu RBP, dlatego target.json wymusza
+// `frame-pointer: always`, a punkty wejścia zerują RBP przed `call kernel_main`.
+// =============================================================================
+
+use core::arch::asm;
+use core::fmt::{self, Write};
+use core::panic::PanicInfo;
+use core::sync::atomic::{AtomicBool, Ordering};
+use crate::interrupts::{self, InterruptFrame};
+use crate::serial;
+
+pub const CRASH_COLOR: u8 = 0x1F; // Biały na niebieskim
+const HEADER_COLOR: u8 = 0x71;
+const PANIC_COLOR: u8 = 0x4F; // Biały na czerwonym
+const BACKTRACE_DEPTH: usize = 12;
+// Identycznościowo zmapowane pierwsze 6 MB - poza tym zakresem RBP jest na pewno śmieciem
+const MAPPED_LIMIT: u64 = 0x600000;
+
+static PANICKING: AtomicBool = AtomicBool::new(false);
+
+const EXCEPTION_NAMES: [&str; 32] = [
+    "#DE Divide Error",
+    "#DB Debug",
+    "NMI Interrupt",
+    "#BP Breakpoint",
+    "#OF Overflow",
+    "#BR BOUND Range Exceeded",
+    "#UD Invalid Opcode",
+    "#NM Device Not Available",
+    "#DF Double Fault",
+    "Coprocessor Segment Overrun",
+    "#TS Invalid TSS",
+    "#NP Segment Not Present",
+    "#SS Stack-Segment Fault",
+    "#GP General Protection",
+    "#PF Page Fault",
+    "Reserved (15)",
+    "#MF x87 Floating-Point Error",
+    "#AC Alignment Check",
+    "#MC Machine Check",
+    "#XM SIMD Floating-Point",
+    "#VE Virtualization Exception",
+    "#CP Control Protection",
+    "Reserved (22)",
+    "Reserved (23)",
+    "Reserved (24)",
+    "Reserved (25)",
+    "Reserved (26)",
+    "Reserved (27)",
+    "#HV Hypervisor Injection",
+    "#VC VMM Communication",
+    "#SX Security Exception",
+    "Reserved (31)",
+];
+
+// Pisze tekst na VGA (z zawijaniem wierszy) i równolegle na port szeregowy
+pub struct CrashWriter {
+    col: u64,
+    row: u64,
+    color: u8,
+}
+
+impl CrashWriter {
+    pub fn new(row: u64, color: u8) -> Self {
+        CrashWriter { col: 0, row, color }
+    }
+}
+
+impl Write for CrashWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for &b in s.as_bytes() {
+            if b == b'\n' || self.col >= 80 {
+                self.col = 0;
+                self.row += 1;
+            }
+            if b != b'\n' {
+                vga_write!(self.col, self.row, b, self.color);
+                self.col += 1;
+            }
+        }
+        serial::write_bytes(s.as_bytes());
+        Ok(())
+    }
+}
+
+// Czyści ekran, rysuje pasek tytułowy i zwraca writer ustawiony pod nim
+pub fn begin_report(title: &[u8], color: u8) -> CrashWriter {
+    interrupts::disable();
+    vga_clear!(color);
+    vga_draw_rect!(0, 0, 80, 1, 0x07);
+    vga_print!(1, 0, HEADER_COLOR, title);
+    serial::write_bytes(b"\n==== ");
+    serial::write_bytes(title);
+    serial::write_bytes(b" ====\n");
+    CrashWriter::new(2, color)
+}
+
+pub fn halt() -> ! {
+    vga_print!(1, 24, HEADER_COLOR, b" System zatrzymany. Zrestartuj maszyne. ");
+    loop {
+        unsafe { asm!("cli", "hlt", options(nomem, nostack)); }
+    }
+}
+
+fn read_cr2() -> u64 {
+    let value: u64;
+    unsafe { asm!("mov {0}, cr2", out(reg) value, options(nomem, nostack, preserves_flags)); }
+    value
+}
+
+fn read_cr3() -> u64 {
+    let value: u64;
+    unsafe { asm!("mov {0}, cr3", out(reg) value, options(nomem, nostack, preserves_flags)); }
+    value
+}
+
+pub fn exception(frame: &InterruptFrame) -> ! {
+    let cr2 = read_cr2();
+    let cr3 = read_cr3();
+    let vector = frame.vector as usize;
+    let name = EXCEPTION_NAMES.get(vector).copied().unwrap_or("Unknown");
+
+    let mut w = begin_report(b"*** KERNEL EXCEPTION ***", CRASH_COLOR);
+    let _ = writeln!(w, "{} (vector {})", name, vector);
+    let _ = writeln!(w, "Error code: {:#018x}", frame.error_code);
+    let _ = writeln!(w);
+    let _ = writeln!(w, "RIP    {:#018x}   CS {:#06x}", frame.rip, frame.cs);
+    let _ = writeln!(w, "RSP    {:#018x}   SS {:#06x}", frame.rsp, frame.ss);
+    let _ = writeln!(w, "RFLAGS {:#018x}", frame.rflags);
+    let _ = writeln!(w, "CR2    {:#018x}", cr2);
+    let _ = writeln!(w, "CR3    {:#018x}", cr3);
+    let _ = writeln!(w);
+    let _ = writeln!(w, "RAX {:#018x}  RBX {:#018x}  RCX {:#018x}", frame.rax, frame.rbx, frame.rcx);
+    let _ = writeln!(w, "RDX {:#018x}  RSI {:#018x}  RDI {:#018x}", frame.rdx, frame.rsi, frame.rdi);
+    let _ = writeln!(w, "RBP {:#018x}  R8  {:#018x}  R9  {:#018x}", frame.rbp, frame.r8, frame.r9);
+    let _ = writeln!(w, "R10 {:#018x}  R11 {:#018x}  R12 {:#018x}", frame.r10, frame.r11, frame.r12);
+    let _ = writeln!(w, "R13 {:#018x}  R14 {:#018x}  R15 {:#018x}", frame.r13, frame.r14, frame.r15);
+    let _ = writeln!(w);
+    write_backtrace(&mut w, frame.rbp);
+    halt();
+}
+
+fn read_rbp() -> u64 {
+    let value: u64;
+    unsafe { asm!("mov {0}, rbp", out(reg) value, options(nomem, nostack, preserves_flags)); }
+    value
+}
+
+// Ramka: [rbp] = RBP wywołującego, [rbp + 8] = adres powrotu
+fn write_backtrace(w: &mut CrashWriter, mut rbp: u64) {
+    let _ = write!(w, "Backtrace:");
+    for depth in 0..BACKTRACE_DEPTH {
+        if rbp == 0 || !rbp.is_multiple_of(8) || rbp + 16 > MAPPED_LIMIT { break; }
+        let (next, ret) = unsafe { (*(rbp as *const u64), *((rbp + 8) as *const u64)) };
+        if ret == 0 { break; }
+        if depth.is_multiple_of(3) { let _ = writeln!(w); }
+        let _ = write!(w, "  #{:<2} {:#018x}", depth, ret);
+        // Stos rośnie w dół - ramki wywołujących muszą leżeć wyżej
+        if next <= rbp { break; }
+        rbp = next;
+    }
+    let _ = writeln!(w);
+}
+
+pub fn panic(info: &PanicInfo) -> ! {
+    // Panic w trakcie rysowania raportu - nie próbujemy drugi raz
+    if PANICKING.swap(true, Ordering::SeqCst) { halt(); }
+
+    let mut w = begin_report(b"*** KERNEL PANIC ***", PANIC_COLOR);
+    let _ = writeln!(w, "{}", info.message());
+    let _ = writeln!(w);
+    match info.location() {
+        Some(location) => {
+            let _ = writeln!(w, "at {}:{}:{}", location.file(), location.line(), location.column());
+        }
+        None => { let _ = writeln!(w, "at <unknown location>"); }
+    }
+    let _ = writeln!(w);
+    write_backtrace(&mut w, read_rbp());
+    halt();
+}
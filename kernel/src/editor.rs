@@ -1,91 +1,398 @@
 use crate::vga;
-use crate::fs::fat16;
+use crate::app::{Action, App};
+use crate::fs::fat16::{self, Dir, Fat16Error, FileName};
+use crate::keyboard::{self, Key, KeyEvent};
+use crate::menu;
 use core::cell::UnsafeCell;
-use core::arch::asm;
+use core::fmt::Write;
 
 const EDITOR_WIDTH: usize = 80;
 const EDITOR_HEIGHT: usize = 22; 
-const CONTENT_COL_OFFSET: usize = 5;
+const CONTENT_COL_OFFSET: usize = 6; // 4 cyfry numeru linii, spacja i '|'
+const LINE_NUMBER_WIDTH: usize = 4;  // 8 KB bufora to najwyżej 8193 linie
 const CONTENT_WIDTH: usize = EDITOR_WIDTH - CONTENT_COL_OFFSET;
 const MAX_FILE_SIZE: usize = 8192; 
+const TAB_WIDTH: usize = 4;
+const TITLE_NAME_WIDTH: usize = 30; // Nazwa pliku w nagłówku, od kolumny 50
+const MAX_EDITS: usize = 256;       // Operacje w historii cofania
+const HISTORY_BYTES: usize = 8192;  // Łączny tekst wszystkich operacji
+const MAX_QUERY: usize = 64;        // Szukany tekst i tekst zamiany
+const STATUS_ROW: u64 = 24;
+const PROMPT_COL: usize = 9;        // Początek wpisywanego tekstu w pasku statusu
+const PROMPT_WIDTH: usize = 45;     // Widoczna część wpisywanego tekstu
+const COLOR_MATCH: u8 = 0x70;       // Trafienia wyszukiwania
+const COLOR_CURRENT_MATCH: u8 = 0x4E; // Bieżące trafienie
 
 pub struct TextEditor {
     content: [u8; MAX_FILE_SIZE],
     size: usize,
-    cursor_x: usize,
-    cursor_y: usize,
-    offset: usize,  
-    filename: [u8; 8], // Zmienione na 8 bajtów (standard FAT16 base)
-    filename_len: usize,
+    cursor_x: usize,     // Kolumna w bieżącej linii
+    cursor_y: usize,     // Numer linii w pliku (od 0)
+    offset: usize,       // Pierwsza linia widoczna na ekranie
+    col_offset: usize,   // Pierwsza kolumna widoczna na ekranie (przewijanie w poziomie)
+    dir: Dir,            // Katalog otwartego pliku
+    filename: FileName,  // Nazwa otwartego pliku (długa albo 8.3)
     modified: bool,
+    history: History,
+    search: Search,
+}
+
+// =============================================================================
+// HISTORIA EDYCJI (Ctrl+Z / Ctrl+Y)
+// =============================================================================
+// Operacje trzymane są na stosie `edits`, a ich tekst kolejno w jednej puli
+// `text` (operacja i zajmuje text[start..start + len]). Po cofnięciu operacje
+// od `done` w górę czekają na ponowienie; każda nowa edycja je porzuca.
+// Gdy brakuje miejsca, znikają najstarsze operacje.
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    Insert,
+    Delete,
+}
+
+#[derive(Clone, Copy)]
+struct Edit {
+    kind: EditKind,
+    pos: usize,    // Miejsce w buforze, od którego tekst wstawiono/usunięto
+    start: usize,  // Początek tekstu operacji w puli
+    len: usize,
+    cursor: usize, // Pozycja kursora przed operacją (tu wraca cofnięcie)
+    linked: bool,  // Cofana i ponawiana razem z poprzednią (zamiana tekstu)
+}
+
+impl Edit {
+    const EMPTY: Edit = Edit { kind: EditKind::Insert, pos: 0, start: 0, len: 0, cursor: 0, linked: false };
+}
+
+struct History {
+    edits: [Edit; MAX_EDITS],
+    count: usize,          // Zapamiętane operacje
+    done: usize,           // Z nich zastosowane (reszta to ponowienia)
+    text: [u8; HISTORY_BYTES],
+    text_len: usize,
+    saved: usize,          // `done` w chwili zapisu
+    saved_lost: bool,      // Zapisanego stanu nie ma już w historii
+    open: bool,            // Ostatnią operację można jeszcze wydłużyć pisaniem
+}
+
+impl History {
+    const EMPTY: History = History {
+        edits: [Edit::EMPTY; MAX_EDITS],
+        count: 0,
+        done: 0,
+        text: [0; HISTORY_BYTES],
+        text_len: 0,
+        saved: 0,
+        saved_lost: false,
+        open: false,
+    };
+
+    // Zerowanie liczników zamiast `= History::EMPTY` - struktura ma ~20 KB,
+    // więcej niż stos jądra. Bieżący tekst staje się stanem zapisanym.
+    fn clear(&mut self) {
+        self.count = 0;
+        self.done = 0;
+        self.text_len = 0;
+        self.saved = 0;
+        self.saved_lost = false;
+        self.open = false;
+    }
+
+    fn text(&self, edit: &Edit) -> &[u8] {
+        &self.text[edit.start..edit.start + edit.len]
+    }
+
+    // Kolejny klawisz zacznie nową operację (ruch kursora, zapis, cofnięcie)
+    fn close(&mut self) {
+        self.open = false;
+    }
+
+    // Ostatnia operacja stanowi jeden krok cofania z poprzednią
+    fn link_last(&mut self) {
+        if self.count > 1 {
+            self.edits[self.count - 1].linked = true;
+        }
+    }
+
+    fn mark_saved(&mut self) {
+        self.saved = self.done;
+        self.saved_lost = false;
+        self.open = false;
+    }
+
+    fn is_saved(&self) -> bool {
+        !self.saved_lost && self.saved == self.done
+    }
+
+    fn drop_oldest(&mut self) {
+        let freed = self.edits[0].len;
+        self.text.copy_within(freed..self.text_len, 0);
+        self.text_len -= freed;
+        self.edits.copy_within(1..self.count, 0);
+        self.count -= 1;
+        self.done -= 1;
+        for edit in &mut self.edits[..self.count] {
+            edit.start -= freed;
+        }
+        // Zapisany stan wypadł z historii - nie da się do niego wrócić
+        if self.saved == 0 {
+            self.saved_lost = true;
+        } else {
+            self.saved -= 1;
+        }
+    }
+
+    // Próbuje dopisać edycję do ostatniej operacji (ciągłe pisanie, seria
+    // Backspace albo Delete). Dopisywać można tylko na końcu puli.
+    fn extend(&mut self, kind: EditKind, pos: usize, bytes: &[u8]) -> bool {
+        if !self.open || self.count == 0 || self.done != self.count { return false; }
+        if self.text_len + bytes.len() > HISTORY_BYTES { return false; }
+        let last = self.edits[self.count - 1];
+        if last.kind != kind { return false; }
+
+        let end = last.start + last.len;
+        if (kind == EditKind::Insert && pos == last.pos + last.len) || (kind == EditKind::Delete && pos == last.pos) {
+            // Dalsze pisanie albo kolejne Delete - tekst na koniec
+            self.text[end..end + bytes.len()].copy_from_slice(bytes);
+        } else if kind == EditKind::Delete && pos + bytes.len() == last.pos {
+            // Kolejny Backspace - tekst na początek, operacja zaczyna się wcześniej
+            self.text.copy_within(last.start..end, last.start + bytes.len());
+            self.text[last.start..last.start + bytes.len()].copy_from_slice(bytes);
+            self.edits[self.count - 1].pos = pos;
+        } else {
+            return false;
+        }
+        self.edits[self.count - 1].len += bytes.len();
+        self.text_len += bytes.len();
+        true
+    }
+
+    // Zapamiętuje edycję już wykonaną na buforze. `group` pozwala dokleić ją
+    // do poprzedniej operacji tego samego rodzaju.
+    fn record(&mut self, kind: EditKind, pos: usize, bytes: &[u8], cursor: usize, group: bool) {
+        if group && self.extend(kind, pos, bytes) { return; }
+
+        // Nowa edycja porzuca ponowienia (razem z zapisanym stanem, jeśli tam był)
+        self.count = self.done;
+        self.text_len = if self.count > 0 {
+            let last = self.edits[self.count - 1];
+            last.start + last.len
+        } else {
+            0
+        };
+        if self.saved > self.done {
+            self.saved_lost = true;
+        }
+
+        if bytes.len() > HISTORY_BYTES {
+            // Operacja większa niż cała pula - historia przepada
+            self.clear();
+            self.saved_lost = true;
+            return;
+        }
+        while self.count == MAX_EDITS || self.text_len + bytes.len() > HISTORY_BYTES {
+            self.drop_oldest();
+        }
+
+        let start = self.text_len;
+        self.text[start..start + bytes.len()].copy_from_slice(bytes);
+        self.text_len += bytes.len();
+        self.edits[self.count] = Edit { kind, pos, start, len: bytes.len(), cursor, linked: false };
+        self.count += 1;
+        self.done = self.count;
+        self.open = group;
+    }
+}
+
+// =============================================================================
+// WYSZUKIWANIE I ZAMIANA (Ctrl+F, F3/Shift+F3, Ctrl+H)
+// =============================================================================
+// Pasek statusu (wiersz 24) służy jako pole tekstowe. Szukanie jest
+// przyrostowe - każdy znak zapytania przesuwa kursor do pierwszego trafienia
+// od miejsca startu. Zamiana idzie od kursora do końca pliku i pyta o każde
+// trafienie; "Wszystkie" zamienia resztę jako jeden krok cofania.
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Edit,
+    Find,        // Ctrl+F - wpisywanie szukanego tekstu
+    ReplaceFind, // Ctrl+H, krok 1 - co zamienić
+    ReplaceWith, // Ctrl+H, krok 2 - na co zamienić
+    Confirm,     // Pytanie o bieżące trafienie
+}
+
+// Komunikat w pasku statusu, widoczny do następnego klawisza
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Notice {
+    None,
+    NotFound,
+    Replaced(usize),
+    NoSpace,
+}
+
+struct Search {
+    mode: Mode,
+    query: [u8; MAX_QUERY],
+    query_len: usize,
+    replacement: [u8; MAX_QUERY],
+    replacement_len: usize,
+    case_sensitive: bool,
+    origin: usize,           // Pozycja kursora w chwili Ctrl+F (Esc tu wraca)
+    current: Option<usize>,  // Początek bieżącego trafienia
+    highlight: bool,         // Podświetlanie wszystkich trafień na ekranie
+    replaced: usize,         // Licznik zamian w bieżącej serii Ctrl+H
+    notice: Notice,
+}
+
+impl Search {
+    const EMPTY: Search = Search {
+        mode: Mode::Edit,
+        query: [0; MAX_QUERY],
+        query_len: 0,
+        replacement: [0; MAX_QUERY],
+        replacement_len: 0,
+        case_sensitive: false,
+        origin: 0,
+        current: None,
+        highlight: false,
+        replaced: 0,
+        notice: Notice::None,
+    };
+
+    fn query(&self) -> &[u8] {
+        &self.query[..self.query_len]
+    }
+
+    fn replacement(&self) -> &[u8] {
+        &self.replacement[..self.replacement_len]
+    }
+
+    // Tekst edytowany w bieżącym kroku (zapytanie albo zamiana)
+    fn input(&mut self) -> (&mut [u8; MAX_QUERY], &mut usize) {
+        if self.mode == Mode::ReplaceWith {
+            (&mut self.replacement, &mut self.replacement_len)
+        } else {
+            (&mut self.query, &mut self.query_len)
+        }
+    }
+}
+
+// Wstawia `bytes` na pozycji `pos`; osobno od TextEditor, żeby tekst mógł
+// pochodzić z historii tego samego edytora
+fn insert_at(content: &mut [u8], size: &mut usize, pos: usize, bytes: &[u8]) {
+    content.copy_within(pos..*size, pos + bytes.len());
+    content[pos..pos + bytes.len()].copy_from_slice(bytes);
+    *size += bytes.len();
 }
 
 struct EditorCell(UnsafeCell<TextEditor>);
 unsafe impl Sync for EditorCell {}
 
-static EDITOR: EditorCell = EditorCell(UnsafeCell::new(TextEditor {
-    content: [0u8; MAX_FILE_SIZE],
-    size: 0,
-    cursor_x: 0,
-    cursor_y: 0,
-    offset: 0,
-    filename: [b' '; 8],
-    filename_len: 0,
-    modified: false,
-}));
+// `new` daje same zera - edytor trafia do .bss, a nie do obrazu jądra
+static EDITOR: EditorCell = EditorCell(UnsafeCell::new(TextEditor::new()));
+
+// Wczytuje plik do edytora; ekran otwiera router po Action::Push(AppId::Editor).
+// Przy błędzie edytor nie powinien być otwierany.
+pub fn open_file(dir: Dir, filename: &FileName) -> Result<(), Fat16Error> {
+    with_editor(|editor| editor.load_file(dir, filename))
+}
 
-#[unsafe(no_mangle)]
-pub fn edit_file(filename: &[u8; 8]) {
-    let editor = unsafe { &mut *EDITOR.0.get() };
-    editor.load_file(filename);
-    editor.run();
+// Edytor jest jeden (8 KB bufora) - dostęp tylko z pętli UI
+pub fn with_editor<R>(f: impl FnOnce(&mut TextEditor) -> R) -> R {
+    f(unsafe { &mut *EDITOR.0.get() })
 }
 
 impl TextEditor {
-    fn new() -> Self {
+    const fn new() -> Self {
         TextEditor {
             content: [0u8; MAX_FILE_SIZE],
             size: 0,
             cursor_x: 0,
             cursor_y: 0,
             offset: 0,
-            filename: [0u8; 8],
-            filename_len: 0,
+            col_offset: 0,
+            dir: Dir::ROOT,
+            filename: FileName::EMPTY,
             modified: false,
+            history: History::EMPTY,
+            search: Search::EMPTY,
         }
     }
 
-    fn load_file(&mut self, filename: &[u8; 8]) {
+    fn load_file(&mut self, dir: Dir, filename: &FileName) -> Result<(), Fat16Error> {
         self.size = 0;
         self.cursor_x = 0;
         self.cursor_y = 0;
+        self.offset = 0;
+        self.col_offset = 0;
         self.modified = false;
+        self.history.clear();
+        self.search.mode = Mode::Edit;
+        self.search.current = None;
+        self.search.highlight = false;
 
-        // 1. Kopiowanie nazwy pliku
-        for i in 0..8 {
-            self.filename[i] = filename[i];
-        }
-
-        // 2. Ładowanie zawartości z FAT16
-        let mut dir_entry_ptr = fat16::ROOT_DIR_START as *mut fat16::Fat16DirEntry;
-        unsafe {
-            for _ in 0..512 {
-                if (*dir_entry_ptr).name == self.filename {
-                    let cluster = (*dir_entry_ptr).first_cluster;
-                    let size = (*dir_entry_ptr).file_size as usize;
-                    let src_addr = fat16::DATA_REGION_START + ((cluster as u32 - 2) * 512);
-                    
-                    self.size = if size > MAX_FILE_SIZE { MAX_FILE_SIZE } else { size };
-                    
-                    // Kopiowanie bajt po bajcie do bufora edytora
-                    for i in 0..self.size {
-                        self.content[i] = *(src_addr as *const u8).add(i);
-                    }
-                    break;
-                }
-                dir_entry_ptr = dir_entry_ptr.add(1);
+        // 1. Katalog i nazwa pliku (zapis trafi do tego samego wpisu)
+        self.dir = dir;
+        self.filename = *filename;
+
+        // 2. Ładowanie zawartości z FAT16 (cały łańcuch klastrów, max 8 KB)
+        self.size = fat16::fat16_load(self.dir, &self.filename, &mut self.content)?;
+        Ok(())
+    }
+
+    // =========================================================================
+    // MODEL TEKSTU - linie rozdzielone '\n'
+    // =========================================================================
+    // Kursor to (cursor_y, cursor_x) = (numer linii, kolumna w linii), liczone
+    // od zera w całym pliku. Pozycja w `content` wyliczana jest skanowaniem
+    // znaków nowej linii - przy 8 KB bufora to tańsze niż utrzymywanie indeksu.
+
+    // Indeks pierwszego bajtu linii `line` (size, gdy takiej linii nie ma)
+    fn line_start(&self, line: usize) -> usize {
+        if line == 0 { return 0; }
+        let mut found = 0;
+        for (i, &b) in self.content[..self.size].iter().enumerate() {
+            if b == b'\n' {
+                found += 1;
+                if found == line { return i + 1; }
             }
         }
+        self.size
+    }
+
+    // Długość linii zaczynającej się od `start` (bez '\n')
+    fn line_len(&self, start: usize) -> usize {
+        self.content[start..self.size].iter().position(|&b| b == b'\n').unwrap_or(self.size - start)
+    }
+
+    // Liczba linii - pusty plik to jedna pusta linia
+    fn line_count(&self) -> usize {
+        self.content[..self.size].iter().filter(|&&b| b == b'\n').count() + 1
+    }
+
+    fn current_line_len(&self) -> usize {
+        self.line_len(self.line_start(self.cursor_y))
+    }
+
+    fn cursor_pos(&self) -> usize {
+        self.line_start(self.cursor_y) + self.cursor_x
+    }
+
+    // Ustawia kursor na bajcie `pos` (po wstawieniu/usunięciu znaku)
+    fn set_cursor_pos(&mut self, pos: usize) {
+        let before = &self.content[..pos];
+        self.cursor_y = before.iter().filter(|&&b| b == b'\n').count();
+        self.cursor_x = match before.iter().rposition(|&b| b == b'\n') {
+            Some(newline) => pos - newline - 1,
+            None => pos,
+        };
+    }
+
+    // Kolumna nie może wyjść poza koniec linii (np. po przejściu do krótszej)
+    fn clamp_cursor_x(&mut self) {
+        let len = self.current_line_len();
+        if self.cursor_x > len { self.cursor_x = len; }
     }
 
     fn draw_editor(&self) {
@@ -93,82 +400,405 @@ impl TextEditor {
         
         // Nagłówek
         vga_print!(0, 0, 0x70, b" Edytor Tekstu v0.1 - [F10] Zapisz  [ESC] Wyjdz ");
+        let (name, name_len) = self.filename.fitted_text(TITLE_NAME_WIDTH);
+        vga_print!(50, 0, 0x70, &name[..name_len]);
+        if self.modified {
+            vga_write!(49, 0, b'*', 0x70); // Niezapisane zmiany
+        }
 
-        // Rysowanie treści
+        // Rysowanie treści - każda linia tekstu w osobnym wierszu ekranu
+        let lines = self.line_count();
+        let mut start = self.line_start(self.offset);
         for row in 0..EDITOR_HEIGHT {
             let actual_row = row + 2;
             if actual_row >= 25 { break; }
+            vga_write!((CONTENT_COL_OFFSET - 1) as u64, actual_row as u64, b'|', 0x1F);
+
+            let line = row + self.offset;
+            if line >= lines { continue; } // Za końcem pliku tylko sam margines
+
+            // Numer linii (wyrównany do prawej)
+            let mut num_buf = vga::LineBuf::new();
+            let _ = write!(num_buf, "{:>1$}", line + 1, LINE_NUMBER_WIDTH);
+            vga_print!(0, actual_row as u64, 0x17, num_buf.as_bytes());
+
+            // Widoczny wycinek linii: od col_offset, najwyżej CONTENT_WIDTH znaków
+            let len = self.line_len(start);
+            if len > self.col_offset {
+                let from = start + self.col_offset;
+                let to = start + len.min(self.col_offset + CONTENT_WIDTH);
+                vga_print!(CONTENT_COL_OFFSET as u64, actual_row as u64, 0x1F, &self.content[from..to]);
+            }
+            if self.search.highlight {
+                self.draw_matches(start, len, actual_row as u64);
+            }
+            start += len + 1;
+        }
+
+        self.draw_status();
+    }
 
-            // Numer linii
-            let line_num = row + self.offset + 1;
-            let mut num_buf = [b' '; 4];
-            num_buf[0] = b'0' + ((line_num / 100) % 10) as u8;
-            num_buf[1] = b'0' + ((line_num / 10) % 10) as u8;
-            num_buf[2] = b'0' + (line_num % 10) as u8;
-            vga_print!(0, actual_row as u64, 0x17, &num_buf);
-            vga_write!(4, actual_row as u64, b'|', 0x1F);
-
-            // Zawartość linii
-            for col in 0..CONTENT_WIDTH {
-                let idx = (row + self.offset) * CONTENT_WIDTH + col;
-                if idx < self.size {
-                    vga_write!((col + CONTENT_COL_OFFSET) as u64, actual_row as u64, self.content[idx], 0x1F);
+    // Podświetla trafienia w linii [start, start + len) - zapytanie nie zawiera
+    // '\n', więc trafienie nigdy nie przechodzi do kolejnej linii
+    fn draw_matches(&self, start: usize, len: usize, row: u64) {
+        let query_len = self.search.query_len;
+        for pos in start..start + len {
+            if !self.match_at(pos) { continue; }
+            let color = if self.search.current == Some(pos) { COLOR_CURRENT_MATCH } else { COLOR_MATCH };
+            for i in pos..pos + query_len {
+                let col = i - start;
+                if col >= self.col_offset && col < self.col_offset + CONTENT_WIDTH {
+                    vga_write!((col - self.col_offset + CONTENT_COL_OFFSET) as u64, row, self.content[i], color);
                 }
+            }
+        }
+    }
+
+    // Ostatnie PROMPT_WIDTH znaków wpisywanego tekstu (koniec jest ważniejszy)
+    fn prompt_text(&self) -> &[u8] {
+        let text = if self.search.mode == Mode::ReplaceWith { self.search.replacement() } else { self.search.query() };
+        &text[text.len().saturating_sub(PROMPT_WIDTH)..]
+    }
+
+    // Pasek statusu: skróty i pozycja kursora, pole tekstowe wyszukiwania
+    // albo pytanie o zamianę
+    fn draw_status(&self) {
+        let mut line = vga::LineBuf::new();
+        let mut color = 0x70;
+        match self.search.mode {
+            Mode::Edit => match self.search.notice {
+                Notice::None => {
+                    let _ = write!(line, " [^F] Szukaj [^H] Zamien [F3] Dalej [^Z] Cofnij [^Y] Ponow");
+                    let _ = write!(line, "{:>1$}", "", 62 - line.as_bytes().len());
+                    let _ = write!(line, "Ln {}, Kol {}", self.cursor_y + 1, self.cursor_x + 1);
+                }
+                Notice::NotFound => {
+                    let _ = write!(line, " Nie znaleziono szukanego tekstu");
+                    color = 0x4F;
                 }
+                Notice::Replaced(count) => { let _ = write!(line, " Zamieniono: {}", count); }
+                Notice::NoSpace => {
+                    let _ = write!(line, " Brak miejsca w buforze ({} B) - zamiana przerwana", MAX_FILE_SIZE);
+                    color = 0x4F;
+                }
+            },
+            Mode::Confirm => {
+                let _ = write!(line, " Zamienic? [T] Tak  [N] Nie  [W] Wszystkie  [ESC] Koniec");
             }
+            mode => {
+                let label = match mode {
+                    Mode::Find => " Szukaj:",
+                    Mode::ReplaceFind => " Zamien:",
+                    _ => " Na:",
+                };
+                let _ = write!(line, "{:<1$}", label, PROMPT_COL + PROMPT_WIDTH + 1);
+                if mode != Mode::ReplaceWith {
+                    let case = if self.search.case_sensitive { "TAK" } else { "NIE" };
+                    let _ = write!(line, "[F4] Wielkosc liter: {}", case);
+                }
+                if self.search.notice == Notice::NotFound {
+                    color = 0x4F; // Brak trafień dla wpisanego zapytania
+                }
+            }
+        }
+        vga_print!(0, STATUS_ROW, color, line.padded());
+        if !matches!(self.search.mode, Mode::Edit | Mode::Confirm) {
+            // Zapytanie wypisujemy osobno - może zawierać polskie glify spoza UTF-8
+            vga_print!(PROMPT_COL as u64, STATUS_ROW, color, self.prompt_text());
         }
+    }
+
+    // Surowe operacje na buforze - bez historii i bez ruchu kursora
+    fn insert_bytes(&mut self, pos: usize, bytes: &[u8]) {
+        insert_at(&mut self.content, &mut self.size, pos, bytes);
+    }
+
+    fn remove_bytes(&mut self, pos: usize, len: usize) {
+        self.content.copy_within(pos + len..self.size, pos);
+        self.size -= len;
+    }
 
     fn insert_char(&mut self, c: u8) {
         if self.size >= MAX_FILE_SIZE { return; }
-        let pos = (self.cursor_y + self.offset) * CONTENT_WIDTH + self.cursor_x;
-        
-        if pos <= self.size {
-            // Przesuwanie zawartości w prawo
-            for i in (pos..self.size).rev() {
-                if i + 1 < MAX_FILE_SIZE {
-                    self.content[i + 1] = self.content[i];
+        let pos = self.cursor_pos();
+
+        self.insert_bytes(pos, &[c]);
+        // Enter zamyka grupę - cofnięcie zdejmuje tekst linia po linii
+        self.history.record(EditKind::Insert, pos, &[c], pos, c != b'\n');
+        self.modified = !self.history.is_saved();
+        self.set_cursor_pos(pos + 1);
+    }
+
+    // Delete - usuwa znak pod kursorem (na końcu linii łączy ją z następną)
+    fn delete_char(&mut self) {
+        let pos = self.cursor_pos();
+        if pos >= self.size { return; }
+
+        let c = self.content[pos];
+        self.remove_bytes(pos, 1);
+        self.history.record(EditKind::Delete, pos, &[c], pos, true);
+        self.modified = !self.history.is_saved();
+    }
+
+    // Backspace - usuwa znak przed kursorem (na początku linii łączy z poprzednią)
+    fn backspace(&mut self) {
+        let pos = self.cursor_pos();
+        if pos == 0 { return; }
+
+        let c = self.content[pos - 1];
+        self.remove_bytes(pos - 1, 1);
+        self.history.record(EditKind::Delete, pos - 1, &[c], pos, true);
+        self.modified = !self.history.is_saved();
+        self.set_cursor_pos(pos - 1);
+    }
+
+    // Ctrl+Z - odwraca ostatnią operację i wraca kursorem tam, gdzie był przed nią
+    fn undo(&mut self) {
+        if self.history.done == 0 { return; }
+        self.history.close();
+
+        // Operacje powiązane (zamiana) cofamy aż do pierwszej z serii
+        let mut cursor = 0;
+        while self.history.done > 0 {
+            self.history.done -= 1;
+            let edit = self.history.edits[self.history.done];
+            match edit.kind {
+                EditKind::Insert => self.remove_bytes(edit.pos, edit.len),
+                EditKind::Delete => {
+                    insert_at(&mut self.content, &mut self.size, edit.pos, self.history.text(&edit));
                 }
             }
-            self.content[pos] = c;
-            self.size += 1;
-            self.modified = true;
-            self.move_cursor_right();
+            cursor = edit.cursor;
+            if !edit.linked { break; }
         }
+        self.modified = !self.history.is_saved();
+        self.set_cursor_pos(cursor);
     }
 
-    fn delete_char(&mut self) {
-        let delete_pos = self.offset + self.cursor_y * CONTENT_WIDTH + self.cursor_x;
+    // Ctrl+Y - ponawia cofniętą operację; kursor staje za jej skutkiem
+    fn redo(&mut self) {
+        if self.history.done == self.history.count { return; }
+        self.history.close();
+
+        let cursor = loop {
+            let edit = self.history.edits[self.history.done];
+            self.history.done += 1;
+            let cursor = match edit.kind {
+                EditKind::Insert => {
+                    insert_at(&mut self.content, &mut self.size, edit.pos, self.history.text(&edit));
+                    edit.pos + edit.len
+                }
+                EditKind::Delete => {
+                    self.remove_bytes(edit.pos, edit.len);
+                    edit.pos
+                }
+            };
+            let done = self.history.done;
+            if done == self.history.count || !self.history.edits[done].linked { break cursor; }
+        };
+        self.modified = !self.history.is_saved();
+        self.set_cursor_pos(cursor);
+    }
+
+    // =========================================================================
+    // WYSZUKIWANIE I ZAMIANA
+    // =========================================================================
+
+    fn match_at(&self, pos: usize) -> bool {
+        let query = self.search.query();
+        if query.is_empty() || pos + query.len() > self.size { return false; }
+        let text = &self.content[pos..pos + query.len()];
+        if self.search.case_sensitive { text == query } else { text.eq_ignore_ascii_case(query) }
+    }
+
+    // Pierwsze trafienie od `from` w przód; `wrap` - potem od początku pliku
+    fn find_forward(&self, from: usize, wrap: bool) -> Option<usize> {
+        let found = (from..self.size).find(|&pos| self.match_at(pos));
+        if found.is_some() || !wrap { return found; }
+        (0..from.min(self.size)).find(|&pos| self.match_at(pos))
+    }
+
+    // Ostatnie trafienie przed `before`, potem od końca pliku
+    fn find_backward(&self, before: usize) -> Option<usize> {
+        (0..before).rev().find(|&pos| self.match_at(pos))
+            .or_else(|| (before..self.size).rev().find(|&pos| self.match_at(pos)))
+    }
 
-        if delete_pos >= self.size {
+    fn jump_to_match(&mut self, pos: usize) {
+        self.search.current = Some(pos);
+        self.search.highlight = true;
+        self.set_cursor_pos(pos);
+    }
+
+    // Ctrl+F / Ctrl+H - pusty pasek, start od bieżącej pozycji
+    fn start_search(&mut self, mode: Mode) {
+        self.search.mode = mode;
+        self.search.query_len = 0;
+        self.search.replacement_len = 0;
+        self.search.origin = self.cursor_pos();
+        self.search.current = None;
+        self.search.highlight = true;
+    }
+
+    // Po każdej zmianie zapytania: pierwsze trafienie od miejsca startu
+    fn incremental_search(&mut self) {
+        let origin = self.search.origin;
+        if self.search.query_len == 0 {
+            self.search.current = None;
+            self.set_cursor_pos(origin);
             return;
         }
+        match self.find_forward(origin, true) {
+            Some(pos) => self.jump_to_match(pos),
+            None => {
+                self.search.current = None;
+                self.search.notice = Notice::NotFound;
+                self.set_cursor_pos(origin);
+            }
+        }
+    }
 
-        // Shift content left
-        for i in delete_pos..(self.size - 1) {
-            self.content[i] = self.content[i + 1];
+    // F3 / Shift+F3 - następne/poprzednie trafienie (z zawinięciem)
+    fn search_next(&mut self, forward: bool) {
+        if self.search.query_len == 0 {
+            self.start_search(Mode::Find);
+            return;
         }
+        let pos = self.cursor_pos();
+        let found = if forward { self.find_forward(pos + 1, true) } else { self.find_backward(pos) };
+        match found {
+            Some(pos) => self.jump_to_match(pos),
+            None => self.search.notice = Notice::NotFound,
+        }
+    }
 
-        self.size -= 1;
-        self.modified = true;
+    // Zamienia bieżące trafienie; `linked` dokleja je do poprzedniej zamiany
+    // w historii (tryb "Wszystkie"). false - zamiana nie zmieściła się w buforze.
+    fn replace_current(&mut self, linked: bool) -> bool {
+        let Some(pos) = self.search.current else { return false; };
+        let (query_len, replacement_len) = (self.search.query_len, self.search.replacement_len);
+        if self.size - query_len + replacement_len > MAX_FILE_SIZE {
+            self.search.notice = Notice::NoSpace;
+            return false;
+        }
+
+        self.history.record(EditKind::Delete, pos, &self.content[pos..pos + query_len], pos, false);
+        if linked { self.history.link_last(); }
+        self.remove_bytes(pos, query_len);
+        if replacement_len > 0 {
+            insert_at(&mut self.content, &mut self.size, pos, self.search.replacement());
+            self.history.record(EditKind::Insert, pos, self.search.replacement(), pos, false);
+            self.history.link_last();
+        }
+        self.modified = !self.history.is_saved();
+        self.search.replaced += 1;
+        self.set_cursor_pos(pos + replacement_len);
+        true
     }
 
-    fn backspace(&mut self) {
-        let pos = (self.cursor_y + self.offset) * CONTENT_WIDTH + self.cursor_x;
-        if pos > 0 && self.size > 0 {
-            for i in (pos - 1)..(self.size - 1) {
-                self.content[i] = self.content[i + 1];
+    // Przejście do następnego trafienia w serii zamian (bez zawijania - inaczej
+    // tekst zamiany zawierający zapytanie byłby zamieniany w kółko)
+    fn confirm_next(&mut self, from: usize) {
+        match self.find_forward(from, false) {
+            Some(pos) => self.jump_to_match(pos),
+            None => self.finish_replace(),
+        }
+    }
+
+    fn finish_replace(&mut self) {
+        self.search.mode = Mode::Edit;
+        self.search.current = None;
+        self.search.highlight = false;
+        // Brak miejsca zgłoszony przez replace_current ma pierwszeństwo
+        if self.search.notice == Notice::None {
+            self.search.notice = Notice::Replaced(self.search.replaced);
+        }
+    }
+
+    fn handle_confirm_input(&mut self, key: Key) {
+        let Some(pos) = self.search.current else { return self.finish_replace(); };
+        match key {
+            Key::Char(b't' | b'T') | Key::Enter => {
+                if !self.replace_current(false) { return self.finish_replace(); }
+                self.confirm_next(pos + self.search.replacement_len);
+            }
+            Key::Char(b'n' | b'N') => self.confirm_next(pos + 1),
+            Key::Char(b'w' | b'W') => {
+                // Reszta trafień bez pytania - jeden krok cofania
+                let mut linked = false;
+                while let Some(pos) = self.search.current {
+                    if !self.replace_current(linked) { break; }
+                    linked = true;
+                    self.search.current = self.find_forward(pos + self.search.replacement_len, false);
+                }
+                self.finish_replace();
             }
-            self.size -= 1;
-            self.modified = true;
-            self.move_cursor_left();
+            Key::Escape => self.finish_replace(),
+            _ => {}
+        }
+    }
+
+    // Klawisze, gdy pasek statusu jest polem tekstowym
+    fn handle_search_input(&mut self, key: Key) {
+        let mode = self.search.mode;
+        if mode == Mode::Confirm {
+            return self.handle_confirm_input(key);
+        }
+        match key {
+            Key::Escape => {
+                self.search.mode = Mode::Edit;
+                self.search.current = None;
+                self.search.highlight = false;
+                if mode == Mode::Find {
+                    self.set_cursor_pos(self.search.origin); // Anulowanie wraca na miejsce
+                }
+            }
+            Key::Enter => match mode {
+                Mode::Find => self.search.mode = Mode::Edit, // Kursor zostaje na trafieniu
+                Mode::ReplaceFind if self.search.query_len > 0 => self.search.mode = Mode::ReplaceWith,
+                Mode::ReplaceWith => {
+                    self.search.replaced = 0;
+                    match self.find_forward(self.search.origin, false) {
+                        Some(pos) => {
+                            self.search.mode = Mode::Confirm;
+                            self.jump_to_match(pos);
+                        }
+                        None => {
+                            self.search.mode = Mode::Edit;
+                            self.search.notice = Notice::NotFound;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Key::F(3) if mode == Mode::Find => self.search_next(!keyboard::shift_down()),
+            Key::F(4) if mode != Mode::ReplaceWith => {
+                self.search.case_sensitive = !self.search.case_sensitive;
+                if mode == Mode::Find { self.incremental_search(); }
+            }
+            Key::Backspace => {
+                let (_, len) = self.search.input();
+                if *len == 0 { return; }
+                *len -= 1;
+                if mode == Mode::Find { self.incremental_search(); }
+            }
+            Key::Char(c) => {
+                let (buf, len) = self.search.input();
+                if *len == MAX_QUERY { return; }
+                buf[*len] = c;
+                *len += 1;
+                if mode == Mode::Find { self.incremental_search(); }
+            }
+            _ => {}
         }
     }
 
     fn move_cursor_right(&mut self) {
-        self.cursor_x += 1;
-        if self.cursor_x >= CONTENT_WIDTH {
-            self.cursor_x = 0;
+        if self.cursor_x < self.current_line_len() {
+            self.cursor_x += 1;
+        } else if self.cursor_y + 1 < self.line_count() {
             self.cursor_y += 1;
+            self.cursor_x = 0;
         }
     }
 
@@ -177,67 +807,557 @@ impl TextEditor {
             self.cursor_x -= 1;
         } else if self.cursor_y > 0 {
             self.cursor_y -= 1;
-            self.cursor_x = CONTENT_WIDTH - 1;
+            self.cursor_x = self.current_line_len();
         }
     }
 
-    fn run(&mut self) {
-        loop {
-            self.draw_editor();
-            // Ustawienie kursora sprzętowego VGA
-            let screen_x = self.cursor_x + CONTENT_COL_OFFSET;
-            let screen_y = self.cursor_y + 2;
-            vga::set_cursor(screen_x as u16, screen_y as u16);
+    fn move_cursor_up(&mut self) {
+        if self.cursor_y > 0 {
+            self.cursor_y -= 1;
+            self.clamp_cursor_x();
+        }
+    }
 
-            let scancode = self.read_scancode();
-            if scancode == 0x01 { break; } // ESC
-            if scancode == 0x44 { self.save_file(); } // F10
-            
-            self.handle_input(scancode);
+    fn move_cursor_down(&mut self) {
+        if self.cursor_y + 1 < self.line_count() {
+            self.cursor_y += 1;
+            self.clamp_cursor_x();
         }
     }
 
-    fn read_scancode(&self) -> u8 {
-        let mut scancode: u8;
-        unsafe {
-            loop {
-                let status: u8;
-                asm!("in al, 0x64", out("al") status);
-                if status & 0x01 != 0 {
-                    asm!("in al, 0x60", out("al") scancode);
-                    if scancode < 0x80 { return scancode; }
-                }
-            }
+    // PageUp/PageDown - kursor i widok przesuwają się o cały ekran
+    fn page_up(&mut self) {
+        self.cursor_y = self.cursor_y.saturating_sub(EDITOR_HEIGHT);
+        self.offset = self.offset.saturating_sub(EDITOR_HEIGHT);
+        self.clamp_cursor_x();
+    }
+
+    fn page_down(&mut self) {
+        let last = self.line_count() - 1;
+        self.cursor_y = (self.cursor_y + EDITOR_HEIGHT).min(last);
+        self.offset = (self.offset + EDITOR_HEIGHT).min(last.saturating_sub(EDITOR_HEIGHT - 1));
+        self.clamp_cursor_x();
+    }
+
+    // Przewija widok tak, by kursor był widoczny (wołane po każdym klawiszu)
+    fn scroll_to_cursor(&mut self) {
+        if self.cursor_y < self.offset {
+            self.offset = self.cursor_y;
+        } else if self.cursor_y >= self.offset + EDITOR_HEIGHT {
+            self.offset = self.cursor_y + 1 - EDITOR_HEIGHT;
+        }
+        if self.cursor_x < self.col_offset {
+            self.col_offset = self.cursor_x;
+        } else if self.cursor_x >= self.col_offset + CONTENT_WIDTH {
+            self.col_offset = self.cursor_x + 1 - CONTENT_WIDTH;
         }
     }
 
-    fn handle_input(&mut self, scancode: u8) {
-        match scancode {
-            0x48 => if self.cursor_y > 0 { self.cursor_y -= 1 }, // Góra
-            0x50 => self.cursor_y += 1,                          // Dół
-            0x4B => self.move_cursor_left(),                     // Lewo
-            0x4D => self.move_cursor_right(),                    // Prawo
-            0x0E => self.backspace(),                            // Backspace
-            0x39 => self.insert_char(b' '),                      // Space
-            0x1C => self.insert_char(b'\n'),                     // Enter
-            _ => {
-                // Mapowanie prostych znaków (QWERTY)
-                let c = match scancode {
-                    0x10..=0x19 => b"qwertyuiop"[(scancode - 0x10) as usize],
-                    0x1E..=0x26 => b"asdfghjkl"[(scancode - 0x1E) as usize],
-                    0x2C..=0x32 => b"zxcvbnm"[(scancode - 0x2C) as usize],
-                    0x33 => b',',
-                    0x34 => b'.',
-                    _ => 0,
-                };
-                if c != 0 { self.insert_char(c); }
+    // Tab wstawia spacje do następnej wielokrotności TAB_WIDTH - bajt 0x09
+    // to w naszym foncie glif 'ż'
+    fn insert_tab(&mut self) {
+        let spaces = TAB_WIDTH - self.cursor_x % TAB_WIDTH;
+        for _ in 0..spaces {
+            self.insert_char(b' ');
+        }
+    }
+
+    fn handle_input(&mut self, key: Key) {
+        let ctrl = keyboard::ctrl_down();
+        // Podświetlenie trafień trwa tylko między kolejnymi F3
+        if key != Key::F(3) {
+            self.search.current = None;
+            self.search.highlight = false;
+        }
+        // Grupę pisania przerywa każdy klawisz poza samą edycją
+        if !matches!(key, Key::Char(_) | Key::Tab | Key::Backspace | Key::Delete) {
+            self.history.close();
+        }
+        match key {
+            Key::Home if ctrl => { self.cursor_y = 0; self.cursor_x = 0; }
+            Key::End if ctrl => {
+                self.cursor_y = self.line_count() - 1;
+                self.cursor_x = self.current_line_len();
             }
+            Key::PageUp => self.page_up(),
+            Key::PageDown => self.page_down(),
+            Key::Up => self.move_cursor_up(),
+            Key::Down => self.move_cursor_down(),
+            Key::Left => self.move_cursor_left(),
+            Key::Right => self.move_cursor_right(),
+            Key::Home => self.cursor_x = 0,
+            Key::End => self.cursor_x = self.current_line_len(),
+            Key::Delete => self.delete_char(),
+            Key::Backspace => self.backspace(),
+            Key::Enter => self.insert_char(b'\n'),
+            Key::Tab => self.insert_tab(),
+            Key::Char(c) => self.insert_char(c), // Litery, cyfry, symbole i polskie glify
+            Key::Ctrl(b'z') => self.undo(),
+            Key::Ctrl(b'y') => self.redo(),
+            Key::Ctrl(b'f') => self.start_search(Mode::Find),
+            Key::Ctrl(b'h') => self.start_search(Mode::ReplaceFind),
+            Key::F(3) => self.search_next(!keyboard::shift_down()),
+            _ => {}
         }
     }
 
-    fn save_file(&mut self) {
+    // Flaga `modified` znika tylko po udanym zapisie
+    fn save_file(&mut self) -> Result<(), Fat16Error> {
         let data = &self.content[..self.size];
-        fat16::fat16_save(&self.filename, data);
+        fat16::fat16_save(self.dir, &self.filename, data)?;
+        self.history.mark_saved();
         self.modified = false;
+        Ok(())
+    }
+}
+
+impl App for TextEditor {
+    fn draw(&self) {
+        self.draw_editor();
+        if !matches!(self.search.mode, Mode::Edit | Mode::Confirm) {
+            // Kursor na końcu wpisywanego tekstu w pasku statusu
+            let col = PROMPT_COL + self.prompt_text().len();
+            vga::set_cursor(col as u16, STATUS_ROW as u16);
+            return;
+        }
+        // Ustawienie kursora sprzętowego VGA
+        let screen_x = self.cursor_x - self.col_offset + CONTENT_COL_OFFSET;
+        let screen_y = self.cursor_y - self.offset + 2;
+        vga::set_cursor(screen_x as u16, screen_y as u16);
+    }
+
+    fn on_key(&mut self, key: KeyEvent) -> Action {
+        // Zwolnienia dekoder zamienia na Key::None, autorepetycja działa jak
+        // kolejne wciśnięcie
+        let key = keyboard::decode(&key);
+        if key == Key::None { return Action::None; }
+        self.search.notice = Notice::None;
+
+        match key {
+            key if self.search.mode != Mode::Edit => self.handle_search_input(key),
+            Key::Escape => return Action::Pop,
+            Key::F(10) => {
+                if let Err(error) = self.save_file() {
+                    return menu::show_error(b" ZAPISZ PLIK ", error);
+                }
+            }
+            key => self.handle_input(key),
+        }
+        self.scroll_to_cursor();
+        self.draw();
+        Action::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Edytor z wczytanym tekstem (jak po load_file) i kursorem na końcu
+    fn editor(text: &[u8]) -> Box<TextEditor> {
+        let mut editor = Box::new(TextEditor::new());
+        editor.insert_bytes(0, text);
+        editor.history.clear();
+        editor.set_cursor_pos(text.len());
+        editor
+    }
+
+    fn type_text(editor: &mut TextEditor, text: &[u8]) {
+        for &c in text {
+            editor.insert_char(c);
+        }
+    }
+
+    fn text(editor: &TextEditor) -> &[u8] {
+        &editor.content[..editor.size]
+    }
+
+    #[test]
+    fn lines_split_on_newlines() {
+        let editor = editor(b"ab\n\ncd\n");
+        // Pusta linia 1 i pusta linia 3 za ostatnim '\n'
+        assert_eq!(editor.line_count(), 4);
+        let starts: Vec<usize> = (0..5).map(|line| editor.line_start(line)).collect();
+        assert_eq!(starts, [0, 3, 4, 7, 7]); // Linii 4 nie ma - koniec pliku
+        let lens: Vec<usize> = starts[..4].iter().map(|&start| editor.line_len(start)).collect();
+        assert_eq!(lens, [2, 0, 2, 0]);
+    }
+
+    #[test]
+    fn empty_file_is_one_empty_line() {
+        let editor = editor(b"");
+        assert_eq!((editor.line_count(), editor.line_start(0), editor.line_len(0)), (1, 0, 0));
+    }
+
+    #[test]
+    fn cursor_position_round_trip() {
+        let mut editor = editor(b"ab\n\ncd\n");
+        // (pozycja w buforze, linia, kolumna) - '\n' należy do końca swojej linii
+        let cases = [(0, 0, 0), (2, 0, 2), (3, 1, 0), (4, 2, 0), (6, 2, 2), (7, 3, 0)];
+        for (pos, line, col) in cases {
+            editor.set_cursor_pos(pos);
+            assert_eq!((editor.cursor_y, editor.cursor_x), (line, col), "pos {}", pos);
+            assert_eq!(editor.cursor_pos(), pos);
+        }
+    }
+
+    #[test]
+    fn cursor_moves_across_line_ends() {
+        let mut editor = editor(b"abcd\n\nxy");
+        editor.set_cursor_pos(4); // Koniec "abcd"
+        editor.move_cursor_right();
+        assert_eq!((editor.cursor_y, editor.cursor_x), (1, 0));
+        editor.move_cursor_left();
+        assert_eq!((editor.cursor_y, editor.cursor_x), (0, 4));
+
+        // W dół przez pustą linię kolumna spada do 0 i już na niej zostaje
+        editor.move_cursor_down();
+        assert_eq!((editor.cursor_y, editor.cursor_x), (1, 0));
+        editor.move_cursor_down();
+        assert_eq!((editor.cursor_y, editor.cursor_x), (2, 0));
+        // Ostatnia linia nie ma '\n' - dalej w dół i w prawo nie da się przejść
+        editor.handle_input(Key::End);
+        editor.move_cursor_down();
+        editor.move_cursor_right();
+        assert_eq!((editor.cursor_y, editor.cursor_x), (2, 2));
+        editor.handle_input(Key::Home);
+        assert_eq!(editor.cursor_pos(), 6);
+    }
+
+    #[test]
+    fn delete_and_backspace_join_lines() {
+        let mut editor = editor(b"ab\ncd");
+        editor.set_cursor_pos(2);
+        editor.delete_char(); // Delete na końcu linii dokleja następną
+        assert_eq!(text(&editor), b"abcd");
+        assert_eq!(editor.line_count(), 1);
+
+        editor.insert_char(b'\n');
+        assert_eq!((editor.cursor_y, editor.cursor_x), (1, 0));
+        editor.backspace(); // Backspace na początku linii wraca na koniec poprzedniej
+        assert_eq!(text(&editor), b"abcd");
+        assert_eq!((editor.cursor_y, editor.cursor_x), (0, 2));
+
+        // Delete na końcu pliku nic nie robi
+        editor.set_cursor_pos(4);
+        editor.delete_char();
+        assert_eq!(text(&editor), b"abcd");
+    }
+
+    // Plik z `lines` liniami "0", "1", ... - numer linii to jej treść
+    fn numbered_lines(lines: usize) -> Box<TextEditor> {
+        let text: Vec<u8> = (0..lines).map(|n| n.to_string()).collect::<Vec<_>>().join("\n").into_bytes();
+        let mut editor = editor(&text);
+        editor.set_cursor_pos(0);
+        editor
+    }
+
+    #[test]
+    fn view_follows_cursor_vertically() {
+        let mut editor = numbered_lines(100);
+        editor.cursor_y = 30;
+        editor.scroll_to_cursor();
+        assert_eq!(editor.offset, 30 + 1 - EDITOR_HEIGHT); // Kursor w ostatnim wierszu ekranu
+        editor.cursor_y = 20;
+        editor.scroll_to_cursor();
+        assert_eq!(editor.offset, 30 + 1 - EDITOR_HEIGHT); // Nadal widoczny - widok stoi
+        editor.cursor_y = 5;
+        editor.scroll_to_cursor();
+        assert_eq!(editor.offset, 5);
+    }
+
+    #[test]
+    fn page_up_and_page_down() {
+        let mut editor = numbered_lines(50);
+        editor.page_down();
+        assert_eq!((editor.cursor_y, editor.offset), (EDITOR_HEIGHT, EDITOR_HEIGHT));
+        // Ostatnia strona: widok kończy się na ostatniej linii, kursor na niej staje
+        editor.page_down();
+        editor.page_down();
+        assert_eq!((editor.cursor_y, editor.offset), (49, 50 - EDITOR_HEIGHT));
+        editor.scroll_to_cursor();
+        assert_eq!(editor.offset, 50 - EDITOR_HEIGHT);
+
+        editor.page_up();
+        assert_eq!((editor.cursor_y, editor.offset), (49 - EDITOR_HEIGHT, 50 - 2 * EDITOR_HEIGHT));
+        editor.page_up();
+        editor.page_up();
+        assert_eq!((editor.cursor_y, editor.offset), (0, 0));
+
+        // Plik krótszy niż ekran się nie przewija
+        let mut short = numbered_lines(5);
+        short.page_down();
+        assert_eq!((short.cursor_y, short.offset), (4, 0));
+    }
+
+    #[test]
+    fn view_follows_cursor_horizontally() {
+        let mut editor = editor(&[b'x'; 200]);
+        editor.scroll_to_cursor(); // Kursor na końcu linii, w kolumnie 200
+        assert_eq!(editor.col_offset, 200 + 1 - CONTENT_WIDTH);
+        editor.cursor_x = 150;
+        editor.scroll_to_cursor();
+        assert_eq!(editor.col_offset, 200 + 1 - CONTENT_WIDTH);
+        editor.handle_input(Key::Home);
+        editor.scroll_to_cursor();
+        assert_eq!(editor.col_offset, 0);
+    }
+
+    #[test]
+    fn shorter_line_pulls_the_view_left() {
+        // Kolumna przycięta do końca krótszej linii, widok wraca za kursorem
+        let mut editor = editor(&[&[b'x'; 200][..], b"\nab"].concat());
+        editor.set_cursor_pos(150);
+        editor.scroll_to_cursor();
+        editor.move_cursor_down();
+        editor.scroll_to_cursor();
+        assert_eq!((editor.cursor_x, editor.col_offset), (2, 2));
+    }
+
+    #[test]
+    fn typing_is_one_undo_step_per_line() {
+        let mut editor = editor(b"");
+        type_text(&mut editor, b"abc");
+        assert_eq!(editor.history.count, 1);
+
+        // Enter zamyka grupę i sam jest osobnym krokiem
+        type_text(&mut editor, b"\nde");
+        assert_eq!(editor.history.count, 3);
+        editor.undo();
+        assert_eq!(text(&editor), b"abc\n");
+        editor.undo();
+        assert_eq!(text(&editor), b"abc");
+        editor.undo();
+        assert_eq!(text(&editor), b"");
+
+        editor.redo();
+        assert_eq!(text(&editor), b"abc");
+        assert_eq!(editor.cursor_pos(), 3);
+    }
+
+    #[test]
+    fn cursor_move_starts_a_new_step() {
+        let mut editor = editor(b"");
+        type_text(&mut editor, b"ab");
+        editor.history.close();
+        type_text(&mut editor, b"cd");
+        assert_eq!(editor.history.count, 2);
+        editor.undo();
+        assert_eq!(text(&editor), b"ab");
+    }
+
+    #[test]
+    fn backspace_and_delete_series_group() {
+        let mut editor = editor(b"hello world");
+        for _ in 0..6 {
+            editor.backspace();
+        }
+        assert_eq!(text(&editor), b"hello");
+        assert_eq!(editor.history.count, 1);
+        assert_eq!(editor.history.text(&editor.history.edits[0]), b" world");
+
+        // Pisanie po Backspace to nowa operacja
+        type_text(&mut editor, b"!");
+        assert_eq!(editor.history.count, 2);
+        editor.undo();
+        editor.undo();
+        assert_eq!(text(&editor), b"hello world");
+        assert_eq!(editor.cursor_pos(), 11);
+
+        editor.set_cursor_pos(0);
+        editor.history.close();
+        for _ in 0..6 {
+            editor.delete_char();
+        }
+        assert_eq!(text(&editor), b"world");
+        assert_eq!(editor.history.text(&editor.history.edits[0]), b"hello ");
+        editor.undo();
+        assert_eq!(text(&editor), b"hello world");
+        assert_eq!(editor.cursor_pos(), 0);
+    }
+
+    #[test]
+    fn oldest_edits_dropped_at_max_edits() {
+        let mut history = Box::new(History::EMPTY);
+        for i in 0..MAX_EDITS + 10 {
+            history.record(EditKind::Insert, i, &[i as u8], i, false);
+        }
+        assert_eq!((history.count, history.done, history.text_len), (MAX_EDITS, MAX_EDITS, MAX_EDITS));
+        assert_eq!(history.text(&history.edits[0]), &[10]);
+        assert_eq!(history.edits[MAX_EDITS - 1].start, MAX_EDITS - 1);
+        // Stan sprzed pierwszej edycji (zapisany) wypadł z historii
+        assert!(!history.is_saved());
+    }
+
+    #[test]
+    fn oldest_edits_dropped_at_history_bytes() {
+        let mut history = Box::new(History::EMPTY);
+        for (i, fill) in [b'a', b'b', b'c'].into_iter().enumerate() {
+            history.record(EditKind::Insert, i * 3000, &[fill; 3000], 0, false);
+        }
+        assert_eq!((history.count, history.text_len), (2, 6000));
+        assert_eq!(history.text(&history.edits[0]), &[b'b'; 3000]);
+        assert_eq!(history.edits[1].start, 3000);
+        assert_eq!(history.text(&history.edits[1]), &[b'c'; 3000]);
+
+        // Operacja większa niż cała pula kasuje historię
+        history.record(EditKind::Delete, 0, &[b'x'; HISTORY_BYTES + 1], 0, false);
+        assert_eq!((history.count, history.done, history.text_len), (0, 0, 0));
+        assert!(!history.is_saved());
+    }
+
+    #[test]
+    fn new_edit_discards_redo() {
+        let mut editor = editor(b"");
+        type_text(&mut editor, b"abc");
+        editor.history.close();
+        type_text(&mut editor, b"def");
+        editor.undo();
+        type_text(&mut editor, b"x");
+        assert_eq!(editor.history.count, 2);
+
+        editor.redo();
+        assert_eq!(text(&editor), b"abcx");
+        editor.undo();
+        editor.undo();
+        editor.redo();
+        editor.redo();
+        assert_eq!(text(&editor), b"abcx");
+    }
+
+    #[test]
+    fn undo_back_to_saved_state_clears_modified() {
+        let mut editor = editor(b"tekst");
+        assert!(!editor.modified);
+        type_text(&mut editor, b"!");
+        assert!(editor.modified);
+        editor.undo();
+        assert!(!editor.modified);
+        editor.redo();
+        assert!(editor.modified);
+
+        // Zapis w połowie historii - tam wraca stan "niezmieniony"
+        editor.history.mark_saved();
+        editor.history.close();
+        type_text(&mut editor, b"?");
+        assert!(editor.modified);
+        editor.undo();
+        assert!(!editor.modified);
+        editor.undo();
+        assert!(editor.modified);
+
+        // Nowa edycja porzuca ponowienie razem z zapisanym stanem
+        type_text(&mut editor, b"#");
+        editor.undo();
+        assert_eq!(text(&editor), b"tekst");
+        assert!(editor.modified);
+    }
+
+    // Zapytanie ustawione tak, jakby wpisano je po Ctrl+F
+    fn find(editor: &mut TextEditor, query: &[u8]) {
+        editor.start_search(Mode::Find);
+        for &c in query {
+            editor.handle_search_input(Key::Char(c));
+        }
+    }
+
+    #[test]
+    fn search_ignores_case_unless_f4() {
+        let mut editor = editor(b"Ala ma KOTA i kota");
+        editor.set_cursor_pos(0);
+        find(&mut editor, b"kota");
+        assert!(editor.match_at(7));
+        assert!(editor.match_at(14));
+        assert_eq!(editor.cursor_pos(), 7); // Przyrostowo - pierwsze trafienie od startu
+
+        editor.handle_search_input(Key::F(4)); // Wielkość liter ma znaczenie
+        assert!(!editor.match_at(7));
+        assert_eq!(editor.cursor_pos(), 14);
+
+        // Esc wraca tam, skąd zaczęto szukać
+        editor.handle_search_input(Key::Escape);
+        assert_eq!(editor.cursor_pos(), 0);
+        assert!(editor.search.mode == Mode::Edit);
+    }
+
+    #[test]
+    fn f3_wraps_around_the_file() {
+        let mut editor = editor(b"xa xb xc");
+        editor.set_cursor_pos(0);
+        find(&mut editor, b"x");
+        editor.handle_search_input(Key::Enter);
+        assert_eq!(editor.cursor_pos(), 0);
+
+        // F3 w przód: 3, 6, potem od początku pliku
+        let mut found = Vec::new();
+        for _ in 0..4 {
+            editor.search_next(true);
+            found.push(editor.cursor_pos());
+        }
+        assert_eq!(found, [3, 6, 0, 3]);
+        // Shift+F3 w tył: 0, potem od końca pliku
+        editor.search_next(false);
+        editor.search_next(false);
+        assert_eq!(editor.cursor_pos(), 6);
+
+        // Bez trafień kursor stoi, pasek pokazuje komunikat
+        find(&mut editor, b"q");
+        editor.handle_search_input(Key::Enter);
+        editor.search_next(true);
+        assert_eq!(editor.cursor_pos(), 6);
+        assert!(editor.search.notice == Notice::NotFound);
+    }
+
+    // Ctrl+H: zapytanie, Enter, tekst zamiany, Enter - pierwsze pytanie
+    fn replace(editor: &mut TextEditor, query: &[u8], replacement: &[u8]) {
+        editor.start_search(Mode::ReplaceFind);
+        for &c in query {
+            editor.handle_search_input(Key::Char(c));
+        }
+        editor.handle_search_input(Key::Enter);
+        for &c in replacement {
+            editor.handle_search_input(Key::Char(c));
+        }
+        editor.handle_search_input(Key::Enter);
+    }
+
+    #[test]
+    fn replace_all_is_one_undo_step() {
+        let mut editor = editor(b"kot, Kot i KOT");
+        editor.set_cursor_pos(0);
+        type_text(&mut editor, b">");
+        editor.history.close();
+        replace(&mut editor, b"kot", b"pies");
+        assert!(editor.search.mode == Mode::Confirm);
+        assert_eq!(editor.search.current, Some(1));
+
+        editor.handle_search_input(Key::Char(b'n')); // Pierwszy zostaje
+        editor.handle_search_input(Key::Char(b'W')); // Reszta bez pytania
+        assert_eq!(text(&editor), b">kot, pies i pies");
+        assert!(editor.search.mode == Mode::Edit);
+        assert!(editor.search.notice == Notice::Replaced(2));
+
+        // Jedno Ctrl+Z cofa wszystkie zamiany, drugie wcześniejsze pisanie
+        editor.undo();
+        assert_eq!(text(&editor), b">kot, Kot i KOT");
+        editor.redo();
+        assert_eq!(text(&editor), b">kot, pies i pies");
+        editor.undo();
+        editor.undo();
+        assert_eq!(text(&editor), b"kot, Kot i KOT");
+    }
+
+    #[test]
+    fn replacement_containing_the_query_is_not_replaced_again() {
+        let mut editor = editor(b"a a");
+        editor.set_cursor_pos(0);
+        replace(&mut editor, b"a", b"aa");
+        editor.handle_search_input(Key::Char(b't'));
+        editor.handle_search_input(Key::Char(b't'));
+        assert_eq!(text(&editor), b"aa aa");
+        assert!(editor.search.notice == Notice::Replaced(2));
+        // Każde potwierdzenie to osobny krok cofania
+        editor.undo();
+        assert_eq!(text(&editor), b"aa a");
     }
 }
@@ -0,0 +1,180 @@
+// =============================================================================
+// URZĄDZENIA BLOKOWE - wspólny interfejs dla RAMDISK-u i dysków
+// =============================================================================
+// System plików widzi tylko numerowane bloki stałej wielkości (LBA), a nie
+// adresy fizyczne. Ten sam sterownik FAT16 działa więc na RAMDISK-u, na dysku
+// ATA i na hoście na buforze `Vec<u8>`.
+// =============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockError {
+    OutOfRange,  // LBA (lub LBA + liczba bloków) poza urządzeniem
+    BadBuffer,   // Długość bufora nie jest wielokrotnością rozmiaru bloku
+    Device,      // Błąd zgłoszony przez sprzęt
+    Timeout,     // Urządzenie nie odpowiedziało w czasie
+}
+
+pub trait BlockDevice {
+    // Rozmiar bloku w bajtach (zwykle 512)
+    fn block_size(&self) -> usize;
+
+    fn block_count(&self) -> u64;
+
+    // Czyta `buf.len() / block_size()` kolejnych bloków od `lba`
+    fn read_blocks(&mut self, lba: u64, buf: &mut [u8]) -> Result<(), BlockError>;
+
+    // Zapisuje `buf.len() / block_size()` kolejnych bloków od `lba`
+    fn write_blocks(&mut self, lba: u64, buf: &[u8]) -> Result<(), BlockError>;
+
+    // Opróżnia bufory zapisu urządzenia (RAMDISK nie ma czego opróżniać)
+    fn flush(&mut self) -> Result<(), BlockError> {
+        Ok(())
+    }
+
+    // Sprawdza zakres i wyrównanie żądania; zwraca offset w bajtach
+    fn check_request(&self, lba: u64, len: usize) -> Result<usize, BlockError> {
+        let block_size = self.block_size();
+        if !len.is_multiple_of(block_size) {
+            return Err(BlockError::BadBuffer);
+        }
+        let blocks = (len / block_size) as u64;
+        if lba.checked_add(blocks).is_none_or(|end| end > self.block_count()) {
+            return Err(BlockError::OutOfRange);
+        }
+        Ok(lba as usize * block_size)
+    }
+}
+
+// Wolumin może pożyczyć urządzenie zamiast je przejmować
+impl<T: BlockDevice + ?Sized> BlockDevice for &mut T {
+    fn block_size(&self) -> usize { (**self).block_size() }
+    fn block_count(&self) -> u64 { (**self).block_count() }
+    fn read_blocks(&mut self, lba: u64, buf: &mut [u8]) -> Result<(), BlockError> {
+        (**self).read_blocks(lba, buf)
+    }
+    fn write_blocks(&mut self, lba: u64, buf: &[u8]) -> Result<(), BlockError> {
+        (**self).write_blocks(lba, buf)
+    }
+    fn flush(&mut self) -> Result<(), BlockError> { (**self).flush() }
+}
+
+// --- RAMDISK ---
+
+pub const RAMDISK_BLOCK_SIZE: usize = 512;
+
+// Urządzenie nad zakresem pamięci: w jądrze obszar RAM pod 0x100000,
+// na hoście dowolny `&mut [u8]` (np. z `Vec<u8>`)
+pub struct RamDisk<'a> {
+    memory: &'a mut [u8],
+}
+
+impl<'a> RamDisk<'a> {
+    pub fn new(memory: &'a mut [u8]) -> Self {
+        RamDisk { memory }
+    }
+}
+
+impl BlockDevice for RamDisk<'_> {
+    fn block_size(&self) -> usize {
+        RAMDISK_BLOCK_SIZE
+    }
+
+    fn block_count(&self) -> u64 {
+        (self.memory.len() / RAMDISK_BLOCK_SIZE) as u64
+    }
+
+    fn read_blocks(&mut self, lba: u64, buf: &mut [u8]) -> Result<(), BlockError> {
+        let offset = self.check_request(lba, buf.len())?;
+        buf.copy_from_slice(&self.memory[offset..offset + buf.len()]);
+        Ok(())
+    }
+
+    fn write_blocks(&mut self, lba: u64, buf: &[u8]) -> Result<(), BlockError> {
+        let offset = self.check_request(lba, buf.len())?;
+        self.memory[offset..offset + buf.len()].copy_from_slice(buf);
+        Ok(())
+    }
+}
+
+// --- PARTYCJE ---
+
+// Fragment urządzenia widziany jako osobne urządzenie (LBA od 0)
+pub struct Partition<D: BlockDevice> {
+    device: D,
+    start: u64,
+    count: u64,
+}
+
+impl<D: BlockDevice> Partition<D> {
+    // None gdy partycja wychodzi poza urządzenie
+    pub fn new(device: D, start: u64, count: u64) -> Option<Self> {
+        let end = start.checked_add(count)?;
+        if count == 0 || end > device.block_count() { return None; }
+        Some(Partition { device, start, count })
+    }
+
+    // Całe urządzenie bez tablicy partycji ("superfloppy", np. mkfs.fat na pliku)
+    pub fn whole(device: D) -> Self {
+        let count = device.block_count();
+        Partition { device, start: 0, count }
+    }
+}
+
+impl<D: BlockDevice> BlockDevice for Partition<D> {
+    fn block_size(&self) -> usize { self.device.block_size() }
+    fn block_count(&self) -> u64 { self.count }
+    fn read_blocks(&mut self, lba: u64, buf: &mut [u8]) -> Result<(), BlockError> {
+        self.check_request(lba, buf.len())?;
+        self.device.read_blocks(self.start + lba, buf)
+    }
+    fn write_blocks(&mut self, lba: u64, buf: &[u8]) -> Result<(), BlockError> {
+        self.check_request(lba, buf.len())?;
+        self.device.write_blocks(self.start + lba, buf)
+    }
+    fn flush(&mut self) -> Result<(), BlockError> { self.device.flush() }
+}
+
+// Wpis tablicy partycji MBR (offset 446, 4 wpisy po 16 bajtów)
+#[derive(Debug, Clone, Copy)]
+pub struct MbrPartition {
+    pub kind: u8,   // Typ partycji (0x04/0x06/0x0E = FAT16)
+    pub start: u64, // Pierwszy sektor (LBA)
+    pub count: u64, // Liczba sektorów
+}
+
+const MBR_SECTOR_SIZE: usize = 512;
+const MBR_TABLE: usize = 446;
+const MBR_ENTRY_SIZE: usize = 16;
+
+impl MbrPartition {
+    pub fn is_fat16(&self) -> bool {
+        matches!(self.kind, 0x04 | 0x06 | 0x0E)
+    }
+}
+
+// Czyta tablicę partycji z sektora 0. Wpisy z błędnym znacznikiem
+// aktywności albo spoza urządzenia są pomijane - sektor 0 bez MBR (np.
+// BPB albo sam bootloader) daje zwykle same None.
+pub fn mbr_partitions<D: BlockDevice>(device: &mut D) -> [Option<MbrPartition>; 4] {
+    let mut table = [None; 4];
+    let mut sector = [0u8; MBR_SECTOR_SIZE];
+    if device.block_size() != MBR_SECTOR_SIZE || device.read_blocks(0, &mut sector).is_err() {
+        return table;
+    }
+    if sector[510..512] != [0x55, 0xAA] { return table; }
+
+    for (i, slot) in table.iter_mut().enumerate() {
+        let raw = &sector[MBR_TABLE + i * MBR_ENTRY_SIZE..][..MBR_ENTRY_SIZE];
+        let start = u32::from_le_bytes([raw[8], raw[9], raw[10], raw[11]]) as u64;
+        let count = u32::from_le_bytes([raw[12], raw[13], raw[14], raw[15]]) as u64;
+        let valid = matches!(raw[0], 0x00 | 0x80)
+            && raw[4] != 0
+            && start > 0
+            && count > 0
+            && start + count <= device.block_count();
+        if valid {
+            *slot = Some(MbrPartition { kind: raw[4], start, count });
+        }
+    }
+    table
+}
@@ -0,0 +1,275 @@
+// =============================================================================
+// SYSTEM PLIKÓW FAT16 - RAMDISK (Bare Metal Implementation)
+// =============================================================================
+// Sterownik woluminu (`volume.rs`) działa na dowolnym `BlockDevice`; tutaj
+// jest tylko część zależna od jądra: wybór dysku (ATA albo RAMDISK w pamięci
+// fizycznej), globalny wolumin i funkcje wołane z UI.
+//
+// Domyślna geometria RAMDISK-u (`Fat16Geometry::RAMDISK`, 4 MB od 0x100000):
+// 0x100000 - Boot Sector (1 sektor)
+// 0x100200 - FAT Table 1 (Tablica powiązań klastrów)
+// 0x104200 - FAT Table 2 (Kopia zapasowa)
+// 0x108200 - Root Directory (Lista plików)
+// 0x10C200 - Data Region (Miejsce na treść plików)
+// =============================================================================
+
+mod volume;
+
+pub use volume::*;
+
+use crate::ata::{self, AtaDrive};
+use crate::rtc;
+use crate::fs::block::{mbr_partitions, BlockDevice, BlockError, Partition, RamDisk};
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+// Obszar RAM pod RAMDISK - musi mieścić się w zmapowanych 6 MB
+pub const RAMDISK_BASE: usize = 0x100000;
+pub const RAMDISK_SIZE: usize = 4 * 1024 * 1024;
+
+impl Fat16Geometry {
+    // 4 MB, klaster = sektor: ten sam układ co dawne stałe 0x100200/0x108200/0x10C200
+    pub const RAMDISK: Fat16Geometry = Fat16Geometry {
+        bytes_per_sector: 512,
+        sectors_per_cluster: 1,
+        reserved_sectors: 1,
+        fat_count: 2,
+        root_entries: 512,
+        total_sectors: (RAMDISK_SIZE / 512) as u32,
+    };
+}
+
+// Urządzenie pod zamontowanym woluminem: partycja FAT16 na dysku ATA
+// (zapis przetrwa restart) albo RAMDISK, gdy żadnego dysku z FAT16 nie ma
+pub enum Disk {
+    Ram(RamDisk<'static>),
+    Ata(Partition<AtaDrive>),
+}
+
+impl BlockDevice for Disk {
+    fn block_size(&self) -> usize {
+        match self {
+            Disk::Ram(disk) => disk.block_size(),
+            Disk::Ata(disk) => disk.block_size(),
+        }
+    }
+
+    fn block_count(&self) -> u64 {
+        match self {
+            Disk::Ram(disk) => disk.block_count(),
+            Disk::Ata(disk) => disk.block_count(),
+        }
+    }
+
+    fn read_blocks(&mut self, lba: u64, buf: &mut [u8]) -> Result<(), BlockError> {
+        match self {
+            Disk::Ram(disk) => disk.read_blocks(lba, buf),
+            Disk::Ata(disk) => disk.read_blocks(lba, buf),
+        }
+    }
+
+    fn write_blocks(&mut self, lba: u64, buf: &[u8]) -> Result<(), BlockError> {
+        match self {
+            Disk::Ram(disk) => disk.write_blocks(lba, buf),
+            Disk::Ata(disk) => disk.write_blocks(lba, buf),
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), BlockError> {
+        match self {
+            Disk::Ram(disk) => disk.flush(),
+            Disk::Ata(disk) => disk.flush(),
+        }
+    }
+}
+
+type KernelVolume = Fat16Volume<Disk>;
+
+// RAMDISK nad zmapowaną pamięcią fizyczną. Wolno go utworzyć tylko raz na
+// wolumin - dwa żywe `RamDisk` aliasowałyby ten sam obszar.
+fn ramdisk() -> Disk {
+    let memory = unsafe { core::slice::from_raw_parts_mut(RAMDISK_BASE as *mut u8, RAMDISK_SIZE) };
+    Disk::Ram(RamDisk::new(memory))
+}
+
+// Szuka FAT16 na dysku: najpierw cały dysk (mkfs.fat na pliku obrazu),
+// potem partycje FAT16 z tablicy MBR
+fn mount_ata(mut drive: AtaDrive) -> Option<KernelVolume> {
+    if let Ok(volume) = Fat16Volume::mount(Disk::Ata(Partition::whole(drive))) {
+        return Some(volume);
+    }
+    for partition in mbr_partitions(&mut drive).into_iter().flatten() {
+        if !partition.is_fat16() { continue; }
+        let Some(disk) = Partition::new(drive, partition.start, partition.count) else { continue };
+        if let Ok(volume) = Fat16Volume::mount(Disk::Ata(disk)) {
+            log_info!("FAT16 partition at LBA {}", partition.start);
+            return Some(volume);
+        }
+    }
+    None
+}
+
+// --- WOLUMIN ---
+
+// Wolumin z buforami sektorów (~8 KB) jako same zera, więc trafia do .bss.
+// `Option::None` nie jest zerami i wciągał całość do .data (obrazu jądra);
+// czy wolumin jest zamontowany, mówi osobna flaga.
+struct VolumeCell(UnsafeCell<MaybeUninit<KernelVolume>>);
+unsafe impl Sync for VolumeCell {}
+
+static VOLUME: VolumeCell = VolumeCell(UnsafeCell::new(MaybeUninit::zeroed()));
+static MOUNTED: AtomicBool = AtomicBool::new(false);
+
+// Montuje pierwszy dysk ATA z systemem FAT16. Bez takiego dysku montuje
+// RAMDISK; gdy pod 0x100000 nie ma poprawnego FAT16 (np. zaraz po starcie),
+// formatuje go domyślną geometrią.
+pub fn init() {
+    let mut volume = None;
+    for drive in ata::probe().into_iter().flatten() {
+        volume = mount_ata(drive);
+        if volume.is_some() {
+            log_info!("using ATA {:?} {} as the file system", drive.channel,
+                      if drive.slave { "slave" } else { "master" });
+            break;
+        }
+    }
+
+    // Nieudany mount zwraca błąd razem z urządzeniem, więc do formatowania
+    // tworzymy RamDisk od nowa (poprzedni już nie istnieje)
+    if volume.is_none() {
+        volume = match Fat16Volume::mount(ramdisk()) {
+            Ok(volume) => Some(volume),
+            Err(_) => {
+                log_info!("formatting RAMDISK at {:#x}", RAMDISK_BASE);
+                Fat16Volume::format(ramdisk(), &Fat16Geometry::RAMDISK)
+                    .inspect_err(|error| log_error!("format failed: {}", error))
+                    .ok()
+            }
+        };
+    }
+    // Znaczniki czasu plików z zegara CMOS
+    if let Some(volume) = volume.as_mut() {
+        volume.set_clock(rtc::now);
+    }
+    if let Some(volume) = volume {
+        unsafe { (*VOLUME.0.get()).write(volume); }
+        MOUNTED.store(true, Ordering::Release);
+
+        // Przy montowaniu tylko raport - naprawa na żądanie (F9 w Mini-Commanderze)
+        match check(CheckMode::Report) {
+            Ok(report) if report.is_clean() => log_info!("check: {}", report),
+            Ok(report) => log_warn!("check: {}", report),
+            Err(_) => {}
+        }
+    }
+}
+
+// Dostęp do zamontowanego woluminu - tylko z pętli UI, bez zagnieżdżania.
+// Każdy błąd trafia do logu tutaj, UI dostaje go do pokazania użytkownikowi.
+pub fn with_volume<R>(f: impl FnOnce(&mut KernelVolume) -> Result<R, Fat16Error>) -> Result<R, Fat16Error> {
+    let result = if MOUNTED.load(Ordering::Acquire) {
+        f(unsafe { (*VOLUME.0.get()).assume_init_mut() })
+    } else {
+        Err(Fat16Error::Io(BlockError::Device))
+    };
+    if let Err(error) = result {
+        log_error!("{}", error);
+    }
+    result
+}
+
+impl Fat16Error {
+    // Komunikat dla okna błędu (bez polskich znaków - font VGA w trybie ASCII)
+    pub fn message(&self) -> &'static [u8] {
+        match self {
+            Fat16Error::NotFound => b"Nie znaleziono pliku",
+            Fat16Error::DirectoryFull => b"Katalog jest pelny",
+            Fat16Error::DiskFull => b"Brak miejsca na dysku",
+            Fat16Error::AlreadyExists => b"Plik o tej nazwie juz istnieje",
+            Fat16Error::InvalidName => b"Niedozwolona nazwa pliku",
+            Fat16Error::NotADirectory => b"To nie jest katalog",
+            Fat16Error::IsADirectory => b"To jest katalog, nie plik",
+            Fat16Error::DirectoryNotEmpty => b"Katalog nie jest pusty",
+            Fat16Error::Corrupt => b"Uszkodzony system plikow - uzyj F9",
+            Fat16Error::Io(_) => b"Blad odczytu/zapisu dysku",
+        }
+    }
+}
+
+// --- FUNKCJE ---
+
+// Pamięć robocza fsck - za duża na stos jądra
+struct ScratchCell(UnsafeCell<CheckScratch>);
+unsafe impl Sync for ScratchCell {}
+
+static CHECK_SCRATCH: ScratchCell = ScratchCell(UnsafeCell::new(CheckScratch::EMPTY));
+
+// Sprawdzenie spójności zamontowanego woluminu (fsck). Jak `with_volume` -
+// tylko z pętli UI, więc nikt inny nie używa w tym czasie CHECK_SCRATCH.
+pub fn check(mode: CheckMode) -> Result<CheckReport, Fat16Error> {
+    with_volume(|v| v.check(mode, unsafe { &mut *CHECK_SCRATCH.0.get() }))
+}
+
+pub fn fat16_save_input_as_file(name: &FileName) -> Result<(), Fat16Error> {
+    // Zapisujemy wpisany tekst ze stanu jądra (pasek INPUT); pusty pasek nic nie zapisuje
+    let (input, text_len) = crate::state::with(|s| (s.input, s.input_len));
+    if text_len == 0 { return Ok(()); }
+    with_volume(|v| v.save(Dir::ROOT, name, &input[..text_len]))
+}
+
+// Pierwszy zajęty wpis katalogu od `index` (z długą nazwą); None na końcu
+pub fn fat16_next_item(dir: Dir, index: usize) -> Result<Option<DirItem>, Fat16Error> {
+    with_volume(|v| v.next_item(dir, index))
+}
+
+pub fn fat16_save(dir: Dir, name: &FileName, data: &[u8]) -> Result<(), Fat16Error> {
+    // Pusty bufor skraca istniejący plik do zera
+    with_volume(|v| v.save(dir, name, data))
+}
+
+// Czyta cały plik (łańcuch klastrów) do `buf`. Zwraca liczbę bajtów albo
+// NotFound, gdy pliku nie ma. Plik dłuższy niż `buf` jest obcinany.
+pub fn fat16_load(dir: Dir, name: &FileName, buf: &mut [u8]) -> Result<usize, Fat16Error> {
+    with_volume(|v| v.load(dir, name, buf))
+}
+
+pub fn fat16_read(path: &[u8]) -> Result<usize, Fat16Error> {
+    // Czyta plik ("/DOCS/NOTES.TXT") i wypisuje go w sekcji podglądu (np. od linii 15).
+    // Zwraca liczbę wypisanych bajtów.
+    let mut buf = [0u8; 80]; // Jeden wiersz ekranu
+    let size = with_volume(|v| {
+        let (dir, name) = v.resolve(path)?;
+        v.load(dir, &name, &mut buf)
+    })?;
+
+    vga_print!(0, 14, 0x0B, b"ZAWARTOSC PLIKU:");
+    for (i, &byte) in buf[..size].iter().enumerate() {
+        vga_write!(i as u64, 15, byte, 0x0F);
+    }
+    Ok(size)
+}
+
+pub fn fat16_truncate(dir: Dir, name: &FileName, new_size: u32) -> Result<(), Fat16Error> {
+    with_volume(|v| v.truncate(dir, name, new_size))
+}
+
+pub fn fat16_create_file(dir: Dir, name: &FileName) -> Result<(), Fat16Error> {
+    with_volume(|v| v.create(dir, name))
+}
+
+pub fn fat16_mkdir(dir: Dir, name: &FileName) -> Result<(), Fat16Error> {
+    with_volume(|v| v.mkdir(dir, name).map(|_| ()))
+}
+
+pub fn fat16_delete(dir: Dir, name: &FileName) -> Result<(), Fat16Error> {
+    with_volume(|v| v.delete(dir, name))
+}
+
+pub fn fat16_rename(dir: Dir, from: &FileName, to: &FileName) -> Result<(), Fat16Error> {
+    with_volume(|v| v.rename(dir, from, to))
+}
+
+pub fn fat16_copy(dir: Dir, from: &FileName, to: &FileName) -> Result<(), Fat16Error> {
+    with_volume(|v| v.copy(dir, from, to))
+}
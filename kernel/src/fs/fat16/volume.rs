@@ -0,0 +1,1894 @@
+// =============================================================================
+// FAT16 - sterownik woluminu nad urządzeniem blokowym
+// =============================================================================
+// Układ woluminu (odczytywany z BPB przy montowaniu, nie zaszyty w kodzie):
+// Boot Sector (BPB) | FAT 1 | FAT 2 ... | Root Directory | Data Region
+// Podkatalogi (atrybut 0x10) leżą w regionie danych jak pliki: łańcuch
+// klastrów z wpisami po 32 B, zaczynający się od `.` i `..`.
+// Długie nazwy (VFAT LFN) to wpisy z atrybutem 0x0F tuż przed wpisem 8.3,
+// po 13 znaków UCS-2; wpis 8.3 niesie wtedy alias w rodzaju PROJEC~1.TXT.
+//
+// Moduł nie dotyka adresów fizycznych, VGA ani asm - całe I/O idzie przez
+// `BlockDevice`, więc kompiluje się również na hoście.
+// Sektor woluminu = blok urządzenia (mount odrzuca inne kombinacje).
+// =============================================================================
+
+use crate::fs::block::{BlockDevice, BlockError};
+use crate::time::date::DateTime;
+
+pub const MAX_SECTOR_SIZE: usize = 4096;
+
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct Fat16DirEntry {
+    pub name: [u8; 8],      // Nazwa pliku (8 znaków)
+    pub ext: [u8; 3],       // Rozszerzenie (3 znaki)
+    pub attr: u8,           // Atrybuty (0x20 = plik archiwalny, 0x10 = katalog)
+    pub reserved: u8,
+    pub creation_time_ms: u8,
+    pub creation_time: u16,
+    pub creation_date: u16,
+    pub last_access_date: u16,
+    pub cluster_high: u16,  // Zawsze 0 w FAT16
+    pub m_time: u16,
+    pub m_date: u16,
+    pub first_cluster: u16, // Indeks pierwszego klastra w danych
+    pub file_size: u32,     // Rozmiar w bajtach
+}
+
+pub const DIR_ENTRY_SIZE: usize = 32;
+
+const DELETED_MARKER: u8 = 0xE5; // Pierwszy bajt nazwy usuniętego wpisu
+
+// Wartości wpisów FAT
+const FAT_FREE: u16 = 0x0000;
+const FAT_EOC: u16 = 0xFFFF;      // Koniec łańcucha (zapisujemy zawsze 0xFFFF)
+const FAT_EOC_MIN: u16 = 0xFFF8;  // ...ale czytając akceptujemy 0xFFF8-0xFFFF
+const FAT_BAD: u16 = 0xFFF7;      // Uszkodzony klaster (nie należy do żadnego pliku)
+
+const ATTR_VOLUME_ID: u8 = 0x08;  // Etykieta woluminu (także część wpisów LFN 0x0F)
+const ATTR_DIRECTORY: u8 = 0x10;
+const ATTR_ARCHIVE: u8 = 0x20;    // Zwykły plik
+const ATTR_LFN: u8 = 0x0F;        // Fragment długiej nazwy (RO | HIDDEN | SYSTEM | VOLUME_ID)
+const ATTR_MASK: u8 = 0x3F;
+
+const MEDIA_FIXED_DISK: u8 = 0xF8;
+const MAX_FAT16_CLUSTERS: usize = 65524; // Więcej klastrów = FAT32
+
+fn get_u16(buf: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([buf[offset], buf[offset + 1]])
+}
+
+fn get_u32(buf: &[u8], offset: usize) -> u32 {
+    get_u16(buf, offset) as u32 | (get_u16(buf, offset + 2) as u32) << 16
+}
+
+fn put(buf: &mut [u8], offset: usize, bytes: &[u8]) {
+    buf[offset..offset + bytes.len()].copy_from_slice(bytes);
+}
+
+// Błąd operacji na woluminie. `Io` niesie błąd urządzenia blokowego; UI
+// pokazuje go użytkownikowi, a nie tylko w logu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fat16Error {
+    NotFound,        // Nie ma pliku o tej nazwie
+    DirectoryFull,   // Brak wolnego wpisu w katalogu
+    DiskFull,        // Za mało wolnych klastrów
+    AlreadyExists,   // Plik docelowy już istnieje
+    InvalidName,     // Nazwa pusta albo z niedozwolonym znakiem
+    NotADirectory,   // Element ścieżki jest plikiem
+    IsADirectory,    // Operacja na pliku wskazała katalog
+    DirectoryNotEmpty,
+    Corrupt,         // Niespójny BPB albo łańcuch klastrów
+    Io(BlockError),
+}
+
+impl From<BlockError> for Fat16Error {
+    fn from(error: BlockError) -> Self {
+        Fat16Error::Io(error)
+    }
+}
+
+impl core::fmt::Display for Fat16Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Fat16Error::NotFound => f.write_str("file not found"),
+            Fat16Error::DirectoryFull => f.write_str("directory full"),
+            Fat16Error::DiskFull => f.write_str("disk full"),
+            Fat16Error::AlreadyExists => f.write_str("file already exists"),
+            Fat16Error::InvalidName => f.write_str("invalid file name"),
+            Fat16Error::NotADirectory => f.write_str("not a directory"),
+            Fat16Error::IsADirectory => f.write_str("is a directory"),
+            Fat16Error::DirectoryNotEmpty => f.write_str("directory not empty"),
+            Fat16Error::Corrupt => f.write_str("file system corrupt"),
+            Fat16Error::Io(error) => write!(f, "block device error: {:?}", error),
+        }
+    }
+}
+
+// --- NAZWY 8.3 ---
+
+// Znaki dozwolone w krótkiej nazwie poza literami i cyframi
+const NAME_SYMBOLS: &[u8] = b"!#$%&'()-@^_`{}~";
+
+fn valid_name_char(c: u8) -> bool {
+    c.is_ascii_uppercase() || c.is_ascii_digit() || c >= 0x80 || NAME_SYMBOLS.contains(&c)
+}
+
+// Krótka nazwa pliku w postaci z katalogu: wielkie litery, nazwa i
+// rozszerzenie dopełnione spacjami. Nowe nazwy powstają tylko przez
+// `parse`/`new`, więc trafiają na dysk już sprawdzone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShortName {
+    name: [u8; 8],
+    ext: [u8; 3],
+}
+
+impl ShortName {
+    // Brak nazwy (np. edytor przed otwarciem pliku) - nie da się jej zapisać
+    pub const BLANK: ShortName = ShortName { name: [b' '; 8], ext: [b' '; 3] };
+    // Wpisy `.` i `..` na początku każdego podkatalogu
+    const DOT: ShortName = ShortName { name: *b".       ", ext: [b' '; 3] };
+    const DOT_DOT: ShortName = ShortName { name: *b"..      ", ext: [b' '; 3] };
+
+    // "readme.txt" -> README  TXT. Bez kropki plik nie ma rozszerzenia;
+    // dłuższe części, druga kropka i niedozwolone znaki dają InvalidName.
+    pub fn parse(text: &[u8]) -> Result<Self, Fat16Error> {
+        let (stem, ext) = match text.iter().position(|&c| c == b'.') {
+            Some(dot) => (&text[..dot], &text[dot + 1..]),
+            None => (text, &[][..]),
+        };
+        if stem.len() > 8 || ext.len() > 3 {
+            return Err(Fat16Error::InvalidName);
+        }
+        // Spacja przed kropką albo na końcu zginęłaby w dopełnieniu
+        if stem.last() == Some(&b' ') || ext.last() == Some(&b' ') {
+            return Err(Fat16Error::InvalidName);
+        }
+        let mut short = ShortName::BLANK;
+        for (dst, src) in short.name.iter_mut().zip(stem) { *dst = src.to_ascii_uppercase(); }
+        for (dst, src) in short.ext.iter_mut().zip(ext) { *dst = src.to_ascii_uppercase(); }
+        Self::new(&short.name, &short.ext)
+    }
+
+    // Pola z wpisu katalogu: niepusta nazwa, bez spacji w środku i bez
+    // znaków, których FAT nie dopuszcza (pierwszy bajt 0xE5 ma znaczenie)
+    pub fn new(name: &[u8; 8], ext: &[u8; 3]) -> Result<Self, Fat16Error> {
+        let valid_part = |part: &[u8]| {
+            let len = part.iter().rposition(|&c| c != b' ').map_or(0, |i| i + 1);
+            part[..len].iter().all(|&c| valid_name_char(c))
+        };
+        if name[0] == b' ' || name[0] == DELETED_MARKER || !valid_part(name) || !valid_part(ext) {
+            return Err(Fat16Error::InvalidName);
+        }
+        Ok(ShortName { name: *name, ext: *ext })
+    }
+
+    // `.` albo `..` - tych wpisów nie wolno zmieniać ani usuwać
+    pub fn is_dot(&self) -> bool {
+        self.name[0] == b'.'
+    }
+
+    // Postać do wyświetlenia: "README.TXT", bez kropki gdy nie ma rozszerzenia
+    pub fn text(&self) -> ([u8; 12], usize) {
+        let trimmed = |part: &[u8]| part.iter().rposition(|&c| c != b' ').map_or(0, |i| i + 1);
+        let mut text = [b' '; 12];
+        let mut len = trimmed(&self.name);
+        text[..len].copy_from_slice(&self.name[..len]);
+        let ext_len = trimmed(&self.ext);
+        if ext_len > 0 {
+            text[len] = b'.';
+            text[len + 1..len + 1 + ext_len].copy_from_slice(&self.ext[..ext_len]);
+            len += 1 + ext_len;
+        }
+        (text, len)
+    }
+}
+
+impl core::fmt::Display for ShortName {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let (text, len) = self.text();
+        for &c in &text[..len] {
+            write!(f, "{}", c as char)?;
+        }
+        Ok(())
+    }
+}
+
+// --- DŁUGIE NAZWY (VFAT LFN) ---
+
+pub const MAX_NAME_LEN: usize = 255;
+
+// Znaki, których nie wolno użyć w długiej nazwie (poza kontrolnymi < 0x20)
+const LFN_FORBIDDEN: &[u8] = b"\"*/:<>?\\|";
+
+const LFN_LAST: u8 = 0x40;         // Znacznik ostatniego fragmentu (pierwszego na dysku)
+const LFN_SEQ_MASK: u8 = 0x1F;
+const LFN_CHARS: usize = 13;       // Znaków UCS-2 w jednym wpisie
+const MAX_LFN_SLOTS: usize = MAX_NAME_LEN.div_ceil(LFN_CHARS);
+// Offsety znaków we wpisie: 5 + 6 + 2 (między nimi atrybut, suma i klaster 0)
+const LFN_OFFSETS: [usize; LFN_CHARS] = [1, 3, 5, 7, 9, 14, 16, 18, 20, 22, 24, 28, 30];
+const LFN_CHECKSUM: usize = 13;
+
+const MAX_ALIAS_TAIL: u32 = 999_999; // PROJEC~1 ... P~999999
+
+// Suma kontrolna aliasu 8.3 zapisywana w każdym fragmencie LFN - fragmenty
+// z inną sumą są osierocone (np. plik zmieniony przez system bez LFN)
+fn lfn_checksum(alias: &ShortName) -> u8 {
+    alias.name.iter().chain(&alias.ext).fold(0u8, |sum, &c| sum.rotate_right(1).wrapping_add(c))
+}
+
+fn fold_case(c: u16) -> u16 {
+    if c < 0x80 { (c as u8).to_ascii_uppercase() as u16 } else { c }
+}
+
+// Nazwa pliku w postaci podanej przez użytkownika albo zapisanej w LFN:
+// do 255 znaków UCS-2, wielkość liter zachowana (porównania ją ignorują).
+// Nazwa, która jest poprawną nazwą 8.3 wielkimi literami, trafia na dysk
+// bez LFN; każda inna dostaje fragmenty LFN i alias.
+#[derive(Clone, Copy)]
+pub struct FileName {
+    chars: [u16; MAX_NAME_LEN],
+    len: usize,
+}
+
+impl FileName {
+    // Brak nazwy (np. edytor przed otwarciem pliku) - nie da się jej zapisać
+    pub const EMPTY: FileName = FileName { chars: [0; MAX_NAME_LEN], len: 0 };
+
+    // Nazwa wpisana w jądrze: drukowalne ASCII ("Notatki z wykladu.txt")
+    pub fn parse(text: &[u8]) -> Result<Self, Fat16Error> {
+        if text.len() > MAX_NAME_LEN || !text.iter().all(|&c| c.is_ascii()) {
+            return Err(Fat16Error::InvalidName);
+        }
+        let mut chars = [0u16; MAX_NAME_LEN];
+        for (dst, &src) in chars.iter_mut().zip(text) { *dst = src as u16; }
+        Self::from_ucs2(&chars[..text.len()])
+    }
+
+    // Nazwa ze znaków UCS-2 (fragmenty LFN, nazwy z hosta). Odrzuca pustą,
+    // za długą, ze znakami kontrolnymi lub zakazanymi, kończącą się spacją
+    // albo kropką (Windows je obcina) - w tym `.` i `..`.
+    pub fn from_ucs2(chars: &[u16]) -> Result<Self, Fat16Error> {
+        let invalid = |c: u16| c < 0x20 || (c < 0x80 && LFN_FORBIDDEN.contains(&(c as u8)));
+        let bad_end = |c: u16| c == b' ' as u16 || c == b'.' as u16;
+        if chars.is_empty() || chars.len() > MAX_NAME_LEN || chars.iter().any(|&c| invalid(c)) {
+            return Err(Fat16Error::InvalidName);
+        }
+        if chars.last().is_some_and(|&c| bad_end(c)) {
+            return Err(Fat16Error::InvalidName);
+        }
+        let mut name = FileName::EMPTY;
+        name.chars[..chars.len()].copy_from_slice(chars);
+        name.len = chars.len();
+        Ok(name)
+    }
+
+    pub fn chars(&self) -> &[u16] {
+        &self.chars[..self.len]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn eq_ignore_case(&self, other: &FileName) -> bool {
+        self.len == other.len && self.chars().iter().zip(other.chars()).all(|(&a, &b)| fold_case(a) == fold_case(b))
+    }
+
+    // Postać do wyświetlenia: znaki spoza ASCII jako `?`
+    pub fn text(&self) -> ([u8; MAX_NAME_LEN], usize) {
+        let mut text = [b' '; MAX_NAME_LEN];
+        for (dst, &c) in text.iter_mut().zip(self.chars()) {
+            *dst = if c < 0x80 { c as u8 } else { b'?' };
+        }
+        (text, self.len)
+    }
+
+    // Jak `text`, ale najwyżej `width` znaków: za długa nazwa traci środek
+    // ("Bardzo dlug~nazwa.txt"), żeby zostało widać rozszerzenie
+    pub fn fitted_text(&self, width: usize) -> ([u8; MAX_NAME_LEN], usize) {
+        let (mut text, len) = self.text();
+        if len <= width || width == 0 { return (text, len.min(width)); }
+        let tail = (width - 1) / 2;
+        let head = width - 1 - tail;
+        text.copy_within(len - tail..len, head + 1);
+        text[head] = b'~';
+        (text, width)
+    }
+
+    // Nazwa, która już jest nazwą 8.3 (wielkie litery) - wpis nie potrzebuje LFN
+    fn as_short_name(&self) -> Option<ShortName> {
+        if self.len > 12 { return None; }
+        let mut text = [0u8; 12];
+        for (dst, &c) in text.iter_mut().zip(self.chars()) {
+            if c >= 0x80 || (c as u8).is_ascii_lowercase() { return None; }
+            *dst = c as u8;
+        }
+        ShortName::parse(&text[..self.len]).ok()
+    }
+
+    // Rdzeń aliasu: wielkie litery, bez spacji i kropek, znaki spoza 8.3
+    // zamienione na `_`; rozszerzenie to pierwsze 3 znaki za ostatnią kropką
+    fn alias_basis(&self) -> ([u8; 8], usize, [u8; 3]) {
+        let dot = b'.' as u16;
+        let chars = self.chars();
+        let start = chars.iter().position(|&c| c != dot && c != b' ' as u16).unwrap_or(chars.len());
+        let chars = &chars[start..];
+        let (stem, ext) = match chars.iter().rposition(|&c| c == dot) {
+            Some(i) => (&chars[..i], &chars[i + 1..]),
+            None => (chars, &[][..]),
+        };
+        let short_char = |&c: &u16| -> Option<u8> {
+            if c == dot || c == b' ' as u16 { return None; }
+            let c = if c < 0x80 { (c as u8).to_ascii_uppercase() } else { b'_' };
+            Some(if valid_name_char(c) { c } else { b'_' })
+        };
+
+        let mut base = [b' '; 8];
+        let mut base_len = 0;
+        for (dst, c) in base.iter_mut().zip(stem.iter().filter_map(short_char)) {
+            *dst = c;
+            base_len += 1;
+        }
+        if base_len == 0 {
+            base[0] = b'_';
+            base_len = 1;
+        }
+        let mut ext3 = [b' '; 3];
+        for (dst, c) in ext3.iter_mut().zip(ext.iter().filter_map(short_char)) { *dst = c; }
+        (base, base_len, ext3)
+    }
+}
+
+impl From<ShortName> for FileName {
+    // Nazwa wpisu bez LFN ("README.TXT"); bajty spoza ASCII przechodzą bez zmian
+    fn from(short: ShortName) -> Self {
+        let (text, len) = short.text();
+        let mut name = FileName::EMPTY;
+        for (dst, &src) in name.chars.iter_mut().zip(&text[..len]) { *dst = src as u16; }
+        name.len = len;
+        name
+    }
+}
+
+impl PartialEq for FileName {
+    fn eq(&self, other: &Self) -> bool {
+        self.chars() == other.chars()
+    }
+}
+
+impl Eq for FileName {}
+
+impl core::fmt::Display for FileName {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        for c in core::char::decode_utf16(self.chars().iter().copied()) {
+            write!(f, "{}", c.unwrap_or(core::char::REPLACEMENT_CHARACTER))?;
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Debug for FileName {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "\"{}\"", self)
+    }
+}
+
+// Składanie długiej nazwy z fragmentów czytanych w kolejności z dysku
+// (od ostatniego fragmentu do pierwszego). Niekompletna sekwencja albo
+// fragmenty z inną sumą kontrolną są ignorowane - zostaje nazwa 8.3.
+struct LfnParts {
+    chars: [u16; MAX_LFN_SLOTS * LFN_CHARS],
+    count: usize,     // Liczba fragmentów sekwencji (0 = brak)
+    next: usize,      // Numer oczekiwanego fragmentu (0 = sekwencja pełna)
+    checksum: u8,
+    first_slot: usize,
+}
+
+impl LfnParts {
+    fn new() -> Self {
+        LfnParts { chars: [0; MAX_LFN_SLOTS * LFN_CHARS], count: 0, next: 0, checksum: 0, first_slot: 0 }
+    }
+
+    fn reset(&mut self) {
+        self.count = 0;
+        self.next = 0;
+    }
+
+    fn push(&mut self, index: usize, entry: &Fat16DirEntry) {
+        let raw = entry.to_bytes();
+        let seq = (raw[0] & LFN_SEQ_MASK) as usize;
+        if raw[0] & LFN_LAST != 0 {
+            if seq == 0 || seq > MAX_LFN_SLOTS {
+                self.reset();
+                return;
+            }
+            self.count = seq;
+            self.checksum = raw[LFN_CHECKSUM];
+            self.first_slot = index;
+        } else if self.next == 0 || seq != self.next || raw[LFN_CHECKSUM] != self.checksum {
+            self.reset(); // Osierocony fragment
+            return;
+        }
+        for (i, &offset) in LFN_OFFSETS.iter().enumerate() {
+            self.chars[(seq - 1) * LFN_CHARS + i] = get_u16(&raw, offset);
+        }
+        self.next = seq - 1;
+    }
+
+    // Długa nazwa dla wpisu 8.3 `alias`, gdy fragmenty są kompletne i do niego należą
+    fn name(&self, alias: &ShortName) -> Option<FileName> {
+        if self.count == 0 || self.next != 0 || self.checksum != lfn_checksum(alias) {
+            return None;
+        }
+        let chars = &self.chars[..self.count * LFN_CHARS];
+        let len = chars.iter().position(|&c| c == 0).unwrap_or(chars.len());
+        FileName::from_ucs2(&chars[..len]).ok()
+    }
+}
+
+// Fragment `seq` (od 1) długiej nazwy; za nazwą terminator 0x0000, dalej 0xFFFF
+fn lfn_entry(name: &FileName, seq: usize, last: bool, checksum: u8) -> Fat16DirEntry {
+    let mut raw = [0u8; DIR_ENTRY_SIZE];
+    raw[0] = seq as u8 | if last { LFN_LAST } else { 0 };
+    raw[11] = ATTR_LFN;
+    raw[LFN_CHECKSUM] = checksum;
+    for (i, &offset) in LFN_OFFSETS.iter().enumerate() {
+        let pos = (seq - 1) * LFN_CHARS + i;
+        let c = match pos.cmp(&name.len) {
+            core::cmp::Ordering::Less => name.chars[pos],
+            core::cmp::Ordering::Equal => 0x0000,
+            core::cmp::Ordering::Greater => 0xFFFF,
+        };
+        put(&mut raw, offset, &c.to_le_bytes());
+    }
+    Fat16DirEntry::from_bytes(&raw)
+}
+
+// Wpis katalogu razem z nazwą do wyświetlenia: długą, gdy poprzedzają go
+// poprawne fragmenty LFN, inaczej nazwą 8.3
+#[derive(Clone, Copy)]
+pub struct DirItem {
+    pub entry: Fat16DirEntry,
+    pub name: FileName,
+    pub index: usize,  // Indeks wpisu 8.3; następny wpis zaczyna się za nim
+    first_slot: usize, // Pierwszy fragment LFN (= index, gdy długiej nazwy nie ma)
+}
+
+// Miejsce na nowy wpis: `lfn_count` fragmentów od `first_slot`, za nimi wpis
+// 8.3 z aliasem. Wyznaczane przed alokacją danych, zapisywane na końcu.
+struct Placement {
+    alias: ShortName,
+    lfn_count: usize,
+    first_slot: usize,
+}
+
+impl Placement {
+    fn index(&self) -> usize {
+        self.first_slot + self.lfn_count
+    }
+}
+
+// --- KATALOGI ---
+
+// Katalog: główny (stały region za tablicami FAT) albo podkatalog w łańcuchu
+// klastrów. Klaster 0 oznacza katalog główny - tak samo jak pole `..` na dysku.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dir(u16);
+
+impl Dir {
+    pub const ROOT: Dir = Dir(0);
+
+    pub fn is_root(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+fn is_path_separator(c: u8) -> bool {
+    c == b'/' || c == b'\\'
+}
+
+impl Fat16DirEntry {
+    // Wpis pustego pliku (bez klastrów) utworzonego w chwili `now`
+    fn new_file(name: &ShortName, now: FatTime) -> Self {
+        Fat16DirEntry {
+            name: name.name,
+            ext: name.ext,
+            attr: ATTR_ARCHIVE,
+            reserved: 0,
+            creation_time_ms: now.tenths,
+            creation_time: now.time,
+            creation_date: now.date,
+            last_access_date: now.date,
+            cluster_high: 0,
+            m_time: now.time,
+            m_date: now.date,
+            first_cluster: 0, // Pusty plik nie ma klastrów
+            file_size: 0,
+        }
+    }
+
+    // Wpis podkatalogu zajmującego łańcuch od `cluster`
+    fn new_dir(name: &ShortName, cluster: u16, now: FatTime) -> Self {
+        let mut entry = Self::new_file(name, now);
+        entry.attr = ATTR_DIRECTORY;
+        entry.first_cluster = cluster;
+        entry
+    }
+
+    pub fn is_volume_label(&self) -> bool {
+        self.attr & ATTR_VOLUME_ID != 0 && !self.is_lfn()
+    }
+
+    // Fragment długiej nazwy - nie jest osobnym plikiem
+    fn is_lfn(&self) -> bool {
+        self.attr & ATTR_MASK == ATTR_LFN
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.attr & ATTR_DIRECTORY != 0
+    }
+
+    // Data i czas ostatniej modyfikacji; None, gdy wpis go nie ma (zera)
+    pub fn modified(&self) -> Option<DateTime> {
+        fat_datetime(self.m_date, self.m_time)
+    }
+
+    // Zmiana zawartości: czas modyfikacji i dzień ostatniego dostępu
+    fn touch(&mut self, now: FatTime) {
+        self.m_date = now.date;
+        self.m_time = now.time;
+        self.last_access_date = now.date;
+    }
+
+    // Katalog wskazywany przez wpis (sensowne tylko dla `is_dir`)
+    pub fn dir(&self) -> Dir {
+        Dir(self.first_cluster)
+    }
+
+    // Nazwa z wpisu bez sprawdzania - pliki z dysku mogą mieć dowolną
+    pub fn short_name(&self) -> ShortName {
+        ShortName { name: self.name, ext: self.ext }
+    }
+
+    // Wpis wolny (nigdy nie użyty) albo usunięty (0xE5)
+    pub fn is_free(&self) -> bool {
+        self.name[0] == 0x00 || self.name[0] == DELETED_MARKER
+    }
+
+    // Dekodowanie 32 bajtów z dysku (little endian niezależnie od platformy)
+    pub fn from_bytes(raw: &[u8]) -> Self {
+        let mut name = [0u8; 8];
+        let mut ext = [0u8; 3];
+        name.copy_from_slice(&raw[0..8]);
+        ext.copy_from_slice(&raw[8..11]);
+        Fat16DirEntry {
+            name,
+            ext,
+            attr: raw[11],
+            reserved: raw[12],
+            creation_time_ms: raw[13],
+            creation_time: get_u16(raw, 14),
+            creation_date: get_u16(raw, 16),
+            last_access_date: get_u16(raw, 18),
+            cluster_high: get_u16(raw, 20),
+            m_time: get_u16(raw, 22),
+            m_date: get_u16(raw, 24),
+            first_cluster: get_u16(raw, 26),
+            file_size: get_u32(raw, 28),
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; DIR_ENTRY_SIZE] {
+        let mut raw = [0u8; DIR_ENTRY_SIZE];
+        put(&mut raw, 0, &self.name);
+        put(&mut raw, 8, &self.ext);
+        raw[11] = self.attr;
+        raw[12] = self.reserved;
+        raw[13] = self.creation_time_ms;
+        put(&mut raw, 14, &{ self.creation_time }.to_le_bytes());
+        put(&mut raw, 16, &{ self.creation_date }.to_le_bytes());
+        put(&mut raw, 18, &{ self.last_access_date }.to_le_bytes());
+        put(&mut raw, 20, &{ self.cluster_high }.to_le_bytes());
+        put(&mut raw, 22, &{ self.m_time }.to_le_bytes());
+        put(&mut raw, 24, &{ self.m_date }.to_le_bytes());
+        put(&mut raw, 26, &{ self.first_cluster }.to_le_bytes());
+        put(&mut raw, 28, &{ self.file_size }.to_le_bytes());
+        raw
+    }
+}
+
+// --- ZNACZNIKI CZASU ---
+
+// Zegar woluminu: w jądrze RTC, w mkimage czas hosta; None = brak zegara
+pub type Clock = fn() -> Option<DateTime>;
+
+// Znacznik czasu w formacie FAT: data (rok-1980) << 9 | miesiąc << 5 | dzień,
+// czas godzina << 11 | minuta << 5 | sekunda/2. Nieparzysta sekunda trafia
+// do `creation_time_ms` (jednostki 10 ms, 0-199).
+#[derive(Debug, Clone, Copy)]
+struct FatTime {
+    date: u16,
+    time: u16,
+    tenths: u8,
+}
+
+impl FatTime {
+    // Epoka FAT (1980-01-01 00:00) - bez zegara albo dla daty spoza 1980-2107
+    const EPOCH: FatTime = FatTime { date: (1 << 5) | 1, time: 0, tenths: 0 };
+
+    fn new(now: DateTime) -> Self {
+        if !now.is_valid() || !(1980..=2107).contains(&now.year) {
+            return Self::EPOCH;
+        }
+        FatTime {
+            date: (now.year - 1980) << 9 | (now.month as u16) << 5 | now.day as u16,
+            time: (now.hour as u16) << 11 | (now.minute as u16) << 5 | (now.second / 2) as u16,
+            tenths: now.second % 2 * 100,
+        }
+    }
+}
+
+// Odwrotność `FatTime::new`; None dla daty 0 (wpis bez znacznika) i dla
+// niemożliwej daty
+fn fat_datetime(date: u16, time: u16) -> Option<DateTime> {
+    let datetime = DateTime {
+        year: 1980 + (date >> 9),
+        month: (date >> 5 & 0x0F) as u8,
+        day: (date & 0x1F) as u8,
+        hour: (time >> 11) as u8,
+        minute: (time >> 5 & 0x3F) as u8,
+        second: ((time & 0x1F) * 2) as u8,
+    };
+    datetime.is_valid().then_some(datetime)
+}
+
+// --- GEOMETRIA I BPB ---
+
+// Parametry formatowania. Rozmiar FAT jest wyliczany w `format`.
+#[derive(Debug, Clone, Copy)]
+pub struct Fat16Geometry {
+    pub bytes_per_sector: u16,
+    pub sectors_per_cluster: u8,
+    pub reserved_sectors: u16,
+    pub fat_count: u8,
+    pub root_entries: u16,
+    pub total_sectors: u32,
+}
+
+impl Fat16Geometry {
+    fn root_sectors(&self) -> u32 {
+        (self.root_entries as u32 * DIR_ENTRY_SIZE as u32).div_ceil(self.bytes_per_sector as u32)
+    }
+
+    // Najmniejszy rozmiar FAT (w sektorach), który opisze wszystkie klastry danych
+    fn fat_sectors(&self) -> u32 {
+        let fixed = self.reserved_sectors as u32 + self.root_sectors();
+        let mut fat_sectors = 1;
+        loop {
+            let data = self.total_sectors.saturating_sub(fixed + fat_sectors * self.fat_count as u32);
+            let clusters = data / self.sectors_per_cluster as u32;
+            let needed = ((clusters + 2) * 2).div_ceil(self.bytes_per_sector as u32);
+            if needed <= fat_sectors { return fat_sectors; }
+            fat_sectors = needed;
+        }
+    }
+}
+
+// Offsety w BPB (boot sector)
+const BPB_BYTES_PER_SECTOR: usize = 11;
+const BPB_SECTORS_PER_CLUSTER: usize = 13;
+const BPB_RESERVED_SECTORS: usize = 14;
+const BPB_FAT_COUNT: usize = 16;
+const BPB_ROOT_ENTRIES: usize = 17;
+const BPB_TOTAL_SECTORS_16: usize = 19;
+const BPB_MEDIA: usize = 21;
+const BPB_FAT_SECTORS: usize = 22;
+const BPB_TOTAL_SECTORS_32: usize = 32;
+const BPB_BOOT_SIGNATURE: usize = 510;
+
+// --- ŁAŃCUCH KLASTRÓW ---
+
+// Kursor po klastrach pliku. Kończy się na EOC, na wpisie spoza dysku i po
+// `cluster_limit` krokach (zapętlony łańcuch nie zawiesi jądra). Nie
+// pożycza woluminu, więc w pętli można po nim pisać.
+struct ChainCursor {
+    next: u16,
+    steps: u16,
+}
+
+impl ChainCursor {
+    fn new(first_cluster: u16) -> Self {
+        ChainCursor { next: first_cluster, steps: 0 }
+    }
+
+    fn advance<D: BlockDevice>(&mut self, volume: &mut Fat16Volume<D>) -> Result<Option<u16>, Fat16Error> {
+        let cluster = self.next;
+        let limit = volume.cluster_limit;
+        if cluster < 2 || cluster >= limit || self.steps >= limit {
+            return Ok(None);
+        }
+        self.steps += 1;
+        self.next = 0;
+        let value = volume.fat_get(cluster)?;
+        if value < FAT_EOC_MIN { self.next = value; }
+        Ok(Some(cluster))
+    }
+}
+
+// --- WOLUMIN ---
+
+// Zamontowany wolumin: położenie regionów (w sektorach) wyliczone z BPB
+pub struct Fat16Volume<D: BlockDevice> {
+    device: D,
+    bytes_per_sector: usize,
+    sectors_per_cluster: usize,
+    fat_count: usize,
+    fat_sectors: usize,    // Rozmiar jednej kopii FAT
+    root_entries: usize,
+    fat_start: u64,        // Pierwszy sektor pierwszej kopii FAT
+    root_start: u64,
+    data_start: u64,
+    cluster_limit: u16,    // Pierwszy numer klastra poza dyskiem
+    // Ostatnio użyty sektor FAT (write-through do wszystkich kopii)
+    fat_cache: [u8; MAX_SECTOR_SIZE],
+    fat_cache_sector: Option<usize>,
+    // Bufor sektora dla katalogu i niepełnych sektorów danych
+    scratch: [u8; MAX_SECTOR_SIZE],
+    clock: Clock,          // Źródło znaczników czasu (domyślnie brak - epoka FAT)
+}
+
+impl<D: BlockDevice> Fat16Volume<D> {
+    // Zapisuje pusty system plików (BPB, FAT-y, katalog główny) na
+    // urządzeniu i montuje go
+    pub fn format(mut device: D, geometry: &Fat16Geometry) -> Result<Self, Fat16Error> {
+        let bps = geometry.bytes_per_sector as usize;
+        if bps != device.block_size() || bps > MAX_SECTOR_SIZE {
+            log_error!("format: sector size {} does not match the device", bps);
+            return Err(Fat16Error::Io(BlockError::BadBuffer));
+        }
+        if geometry.total_sectors as u64 > device.block_count() {
+            log_error!("format: {} sectors do not fit on the device", geometry.total_sectors);
+            return Err(Fat16Error::Io(BlockError::OutOfRange));
+        }
+
+        let fat_sectors = geometry.fat_sectors();
+        let metadata_sectors = geometry.reserved_sectors as u64
+            + fat_sectors as u64 * geometry.fat_count as u64
+            + geometry.root_sectors() as u64;
+        let mut sector = [0u8; MAX_SECTOR_SIZE];
+        let sector = &mut sector[..bps];
+
+        // Czyścimy boot sector, wszystkie FAT-y i cały katalog główny
+        for lba in 0..metadata_sectors {
+            device.write_blocks(lba, sector)?;
+        }
+
+        // 1. BIOS Parameter Block (BPB)
+        put(sector, 0, &[0xEB, 0x3C, 0x90]);  // jmp short + nop
+        put(sector, 3, b"RUSTEDOS");          // OEM
+        put(sector, BPB_BYTES_PER_SECTOR, &geometry.bytes_per_sector.to_le_bytes());
+        put(sector, BPB_SECTORS_PER_CLUSTER, &[geometry.sectors_per_cluster]);
+        put(sector, BPB_RESERVED_SECTORS, &geometry.reserved_sectors.to_le_bytes());
+        put(sector, BPB_FAT_COUNT, &[geometry.fat_count]);
+        put(sector, BPB_ROOT_ENTRIES, &geometry.root_entries.to_le_bytes());
+        if geometry.total_sectors <= 0xFFFF {
+            put(sector, BPB_TOTAL_SECTORS_16, &(geometry.total_sectors as u16).to_le_bytes());
+        } else {
+            put(sector, BPB_TOTAL_SECTORS_32, &geometry.total_sectors.to_le_bytes());
+        }
+        put(sector, BPB_MEDIA, &[MEDIA_FIXED_DISK]);
+        put(sector, BPB_FAT_SECTORS, &(fat_sectors as u16).to_le_bytes());
+
+        // 2. Rozszerzony BPB (EBPB) FAT16
+        put(sector, 36, &[0x80, 0x00, 0x29]);  // Dysk 0x80, sygnatura 0x29
+        put(sector, 39, &0x5275_7374u32.to_le_bytes()); // Volume ID "Rust"
+        put(sector, 43, b"RUSTED     ");
+        put(sector, 54, b"FAT16   ");
+        put(sector, BPB_BOOT_SIGNATURE, &[0x55, 0xAA]);
+        device.write_blocks(0, sector)?;
+
+        // 3. Inicjalizacja tablic FAT (klastry zarezerwowane)
+        // Pierwsze dwa wpisy w FAT muszą być specjalne (F8 FF FF FF)
+        sector.fill(0);
+        put(sector, 0, &(0xFF00 | MEDIA_FIXED_DISK as u16).to_le_bytes());
+        put(sector, 2, &FAT_EOC.to_le_bytes());
+        for fat in 0..geometry.fat_count as u64 {
+            let lba = geometry.reserved_sectors as u64 + fat * fat_sectors as u64;
+            device.write_blocks(lba, sector)?;
+        }
+        device.flush()?;
+
+        Self::mount(device)
+    }
+
+    // Czyta BPB z sektora 0 i wylicza układ woluminu. Odrzuca wolumin, którego
+    // parametry są niespójne albo nie pasują do urządzenia (Corrupt).
+    pub fn mount(mut device: D) -> Result<Self, Fat16Error> {
+        let reject = |reason: &str| {
+            log_warn!("mount rejected: {}", reason);
+            Err(Fat16Error::Corrupt)
+        };
+
+        let block_size = device.block_size();
+        if block_size > MAX_SECTOR_SIZE {
+            return reject("device block size too large");
+        }
+        let mut boot = [0u8; MAX_SECTOR_SIZE];
+        let boot = &mut boot[..block_size];
+        device.read_blocks(0, boot)?;
+
+        if get_u16(boot, BPB_BOOT_SIGNATURE) != 0xAA55 {
+            return reject("missing 0x55AA boot signature");
+        }
+        let bytes_per_sector = get_u16(boot, BPB_BYTES_PER_SECTOR) as usize;
+        let sectors_per_cluster = boot[BPB_SECTORS_PER_CLUSTER] as usize;
+        let reserved_sectors = get_u16(boot, BPB_RESERVED_SECTORS) as usize;
+        let fat_count = boot[BPB_FAT_COUNT] as usize;
+        let root_entries = get_u16(boot, BPB_ROOT_ENTRIES) as usize;
+        let fat_sectors = get_u16(boot, BPB_FAT_SECTORS) as usize;
+        let media = boot[BPB_MEDIA];
+        let total_sectors = match get_u16(boot, BPB_TOTAL_SECTORS_16) {
+            0 => get_u32(boot, BPB_TOTAL_SECTORS_32) as usize,
+            n => n as usize,
+        };
+
+        if !matches!(bytes_per_sector, 512 | 1024 | 2048 | 4096) {
+            return reject("bytes per sector not 512/1024/2048/4096");
+        }
+        if bytes_per_sector != block_size {
+            return reject("sector size differs from the device block size");
+        }
+        if !sectors_per_cluster.is_power_of_two() || sectors_per_cluster > 128 {
+            return reject("sectors per cluster not a power of two <= 128");
+        }
+        if reserved_sectors == 0 || fat_count == 0 || fat_sectors == 0 || root_entries == 0 {
+            return reject("zero reserved sectors, FAT count, FAT size or root entries");
+        }
+        if !(root_entries * DIR_ENTRY_SIZE).is_multiple_of(bytes_per_sector) {
+            return reject("root directory does not fill whole sectors");
+        }
+        if total_sectors as u64 > device.block_count() {
+            return reject("volume larger than the device");
+        }
+
+        let root_sectors = root_entries * DIR_ENTRY_SIZE / bytes_per_sector;
+        let data_sector = reserved_sectors + fat_count * fat_sectors + root_sectors;
+        if data_sector >= total_sectors {
+            return reject("no room for the data region");
+        }
+        let clusters = (total_sectors - data_sector) / sectors_per_cluster;
+        if clusters == 0 || clusters > MAX_FAT16_CLUSTERS {
+            return reject("cluster count outside the FAT16 range");
+        }
+        if fat_sectors * bytes_per_sector / 2 < clusters + 2 {
+            return reject("FAT too small for the cluster count");
+        }
+
+        let mut volume = Fat16Volume {
+            device,
+            bytes_per_sector,
+            sectors_per_cluster,
+            fat_count,
+            fat_sectors,
+            root_entries,
+            fat_start: reserved_sectors as u64,
+            root_start: (reserved_sectors + fat_count * fat_sectors) as u64,
+            data_start: data_sector as u64,
+            cluster_limit: (clusters + 2) as u16,
+            fat_cache: [0; MAX_SECTOR_SIZE],
+            fat_cache_sector: None,
+            scratch: [0; MAX_SECTOR_SIZE],
+            clock: || None,
+        };
+        if volume.fat_get(0)? != 0xFF00 | media as u16 {
+            return reject("FAT[0] does not match the media descriptor");
+        }
+
+        log_info!(
+            "mounted: {} clusters of {} B, {} FATs, {} root entries",
+            clusters, volume.bytes_per_cluster(), fat_count, root_entries
+        );
+        Ok(volume)
+    }
+
+    // Zegar dla znaczników czasu tworzonych i zmienianych wpisów
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+    }
+
+    fn now(&self) -> FatTime {
+        (self.clock)().map_or(FatTime::EPOCH, FatTime::new)
+    }
+
+    fn bytes_per_cluster(&self) -> usize {
+        self.bytes_per_sector * self.sectors_per_cluster
+    }
+
+    fn clusters_for(&self, size: usize) -> usize {
+        size.div_ceil(self.bytes_per_cluster())
+    }
+
+    // --- TABLICA FAT ---
+
+    fn load_fat_sector(&mut self, sector: usize) -> Result<(), Fat16Error> {
+        if self.fat_cache_sector == Some(sector) { return Ok(()); }
+        self.fat_cache_sector = None;
+        let bps = self.bytes_per_sector;
+        self.device.read_blocks(self.fat_start + sector as u64, &mut self.fat_cache[..bps])?;
+        self.fat_cache_sector = Some(sector);
+        Ok(())
+    }
+
+    fn fat_get(&mut self, cluster: u16) -> Result<u16, Fat16Error> {
+        let offset = cluster as usize * 2;
+        self.load_fat_sector(offset / self.bytes_per_sector)?;
+        Ok(get_u16(&self.fat_cache, offset % self.bytes_per_sector))
+    }
+
+    // Zapis trafia do wszystkich kopii FAT
+    fn fat_set(&mut self, cluster: u16, value: u16) -> Result<(), Fat16Error> {
+        let offset = cluster as usize * 2;
+        let sector = offset / self.bytes_per_sector;
+        self.load_fat_sector(sector)?;
+        put(&mut self.fat_cache, offset % self.bytes_per_sector, &value.to_le_bytes());
+
+        let bps = self.bytes_per_sector;
+        for fat in 0..self.fat_count {
+            let lba = self.fat_start + (fat * self.fat_sectors + sector) as u64;
+            self.device.write_blocks(lba, &self.fat_cache[..bps])?;
+        }
+        Ok(())
+    }
+
+    // --- ŁAŃCUCHY KLASTRÓW ---
+
+    fn cluster_lba(&self, cluster: u16) -> u64 {
+        // Sektor = Początek_Danych + (Cluster - 2) * Sektory_na_Klaster
+        self.data_start + (cluster as u64 - 2) * self.sectors_per_cluster as u64
+    }
+
+    fn chain_len(&mut self, first_cluster: u16) -> Result<usize, Fat16Error> {
+        let mut cursor = ChainCursor::new(first_cluster);
+        let mut len = 0;
+        while cursor.advance(self)?.is_some() { len += 1; }
+        Ok(len)
+    }
+
+    // Klaster o indeksie `index` w łańcuchu (0 = pierwszy)
+    fn chain_nth(&mut self, first_cluster: u16, index: usize) -> Result<Option<u16>, Fat16Error> {
+        let mut cursor = ChainCursor::new(first_cluster);
+        for _ in 0..index {
+            if cursor.advance(self)?.is_none() { return Ok(None); }
+        }
+        cursor.advance(self)
+    }
+
+    fn chain_last(&mut self, first_cluster: u16) -> Result<Option<u16>, Fat16Error> {
+        let mut cursor = ChainCursor::new(first_cluster);
+        let mut last = None;
+        while let Some(cluster) = cursor.advance(self)? { last = Some(cluster); }
+        Ok(last)
+    }
+
+    fn free_cluster_count(&mut self) -> Result<usize, Fat16Error> {
+        let mut count = 0;
+        for cluster in 2..self.cluster_limit {
+            if self.fat_get(cluster)? == FAT_FREE { count += 1; }
+        }
+        Ok(count)
+    }
+
+    // Alokuje `count` (> 0) klastrów i łączy je w łańcuch doklejony za `tail`
+    // (0 = nowy łańcuch). Zwraca pierwszy nowy klaster. Gdy miejsca nie
+    // wystarczy, FAT pozostaje nietknięty (DiskFull).
+    fn extend_chain(&mut self, tail: u16, count: usize) -> Result<u16, Fat16Error> {
+        if self.free_cluster_count()? < count {
+            return Err(Fat16Error::DiskFull);
+        }
+
+        let mut first = 0;
+        let mut prev = tail;
+        let mut allocated = 0;
+        for cluster in 2..self.cluster_limit {
+            if allocated == count { break; }
+            if self.fat_get(cluster)? != FAT_FREE { continue; }
+
+            self.fat_set(cluster, FAT_EOC)?;
+            if prev != 0 { self.fat_set(prev, cluster)?; }
+            if first == 0 { first = cluster; }
+            prev = cluster;
+            allocated += 1;
+        }
+        Ok(first)
+    }
+
+    fn alloc_chain(&mut self, count: usize) -> Result<u16, Fat16Error> {
+        self.extend_chain(0, count)
+    }
+
+    // Zwalnia łańcuch od `first_cluster` do końca
+    fn free_chain(&mut self, first_cluster: u16) -> Result<(), Fat16Error> {
+        let mut cluster = first_cluster;
+        let mut steps = 0;
+        while cluster >= 2 && cluster < self.cluster_limit && steps < self.cluster_limit {
+            let next = self.fat_get(cluster)?;
+            self.fat_set(cluster, FAT_FREE)?;
+            cluster = if next >= FAT_EOC_MIN { 0 } else { next };
+            steps += 1;
+        }
+        Ok(())
+    }
+
+    // Skraca łańcuch do `keep` klastrów (0 = zwalnia całość). Zwraca nowy
+    // pierwszy klaster (0 dla pustego pliku).
+    fn truncate_chain(&mut self, first_cluster: u16, keep: usize) -> Result<u16, Fat16Error> {
+        if keep == 0 {
+            self.free_chain(first_cluster)?;
+            return Ok(0);
+        }
+        if let Some(last) = self.chain_nth(first_cluster, keep - 1)? {
+            let rest = self.fat_get(last)?;
+            if rest < FAT_EOC_MIN {
+                self.fat_set(last, FAT_EOC)?;
+                self.free_chain(rest)?;
+            }
+        }
+        Ok(first_cluster)
+    }
+
+    // Zmienia długość łańcucha na `clusters` klastrów. Zwraca nowy pierwszy
+    // klaster; przy braku miejsca łańcuch pozostaje bez zmian.
+    fn resize_chain(&mut self, first_cluster: u16, clusters: usize) -> Result<u16, Fat16Error> {
+        let current = self.chain_len(first_cluster)?;
+        if clusters <= current {
+            return self.truncate_chain(first_cluster, clusters);
+        }
+        match self.chain_last(first_cluster)? {
+            Some(last) => self.extend_chain(last, clusters - current).map(|_| first_cluster),
+            None => self.alloc_chain(clusters),
+        }
+    }
+
+    // --- DANE ---
+
+    // Zapis jednego sektora danych; niepełny sektor dopełniamy zerami
+    fn write_data_sector(&mut self, lba: u64, part: &[u8]) -> Result<(), Fat16Error> {
+        let bps = self.bytes_per_sector;
+        if part.len() == bps {
+            return Ok(self.device.write_blocks(lba, part)?);
+        }
+        self.scratch[..part.len()].copy_from_slice(part);
+        self.scratch[part.len()..bps].fill(0);
+        Ok(self.device.write_blocks(lba, &self.scratch[..bps])?)
+    }
+
+    fn read_data_sector(&mut self, lba: u64, part: &mut [u8]) -> Result<(), Fat16Error> {
+        let bps = self.bytes_per_sector;
+        if part.len() == bps {
+            return Ok(self.device.read_blocks(lba, part)?);
+        }
+        self.device.read_blocks(lba, &mut self.scratch[..bps])?;
+        part.copy_from_slice(&self.scratch[..part.len()]);
+        Ok(())
+    }
+
+    // Kopiuje dane do kolejnych klastrów łańcucha. Łańcuch krótszy niż dane
+    // oznacza uszkodzony FAT (Corrupt).
+    fn write_chain(&mut self, first_cluster: u16, data: &[u8]) -> Result<(), Fat16Error> {
+        let mut cursor = ChainCursor::new(first_cluster);
+        for part in data.chunks(self.bytes_per_cluster()) {
+            let cluster = cursor.advance(self)?.ok_or(Fat16Error::Corrupt)?;
+            let lba = self.cluster_lba(cluster);
+            for (i, sector) in part.chunks(self.bytes_per_sector).enumerate() {
+                self.write_data_sector(lba + i as u64, sector)?;
+            }
+        }
+        Ok(self.device.flush()?)
+    }
+
+    // Czyta do `size` bajtów pliku do `buf`, zwraca liczbę skopiowanych bajtów
+    fn read_chain(&mut self, first_cluster: u16, size: usize, buf: &mut [u8]) -> Result<usize, Fat16Error> {
+        let size = size.min(buf.len());
+        let mut cursor = ChainCursor::new(first_cluster);
+        let mut copied = 0;
+        while copied < size {
+            let Some(cluster) = cursor.advance(self)? else { break };
+            let lba = self.cluster_lba(cluster);
+            for i in 0..self.sectors_per_cluster {
+                if copied >= size { break; }
+                let len = (size - copied).min(self.bytes_per_sector);
+                self.read_data_sector(lba + i as u64, &mut buf[copied..copied + len])?;
+                copied += len;
+            }
+        }
+        Ok(copied)
+    }
+
+    // --- KATALOGI ---
+
+    // Sektor i offset wpisu `index`; None za końcem katalogu (katalog główny
+    // ma stały rozmiar, podkatalog kończy się wraz z łańcuchem klastrów)
+    fn dir_entry_location(&mut self, dir: Dir, index: usize) -> Result<Option<(u64, usize)>, Fat16Error> {
+        let offset = index * DIR_ENTRY_SIZE;
+        let bps = self.bytes_per_sector;
+        if dir.is_root() {
+            if index >= self.root_entries { return Ok(None); }
+            return Ok(Some((self.root_start + (offset / bps) as u64, offset % bps)));
+        }
+        let bpc = self.bytes_per_cluster();
+        let Some(cluster) = self.chain_nth(dir.0, offset / bpc)? else { return Ok(None) };
+        let in_cluster = offset % bpc;
+        Ok(Some((self.cluster_lba(cluster) + (in_cluster / bps) as u64, in_cluster % bps)))
+    }
+
+    fn read_dir_entry(&mut self, dir: Dir, index: usize) -> Result<Option<Fat16DirEntry>, Fat16Error> {
+        let Some((lba, offset)) = self.dir_entry_location(dir, index)? else { return Ok(None) };
+        let bps = self.bytes_per_sector;
+        self.device.read_blocks(lba, &mut self.scratch[..bps])?;
+        Ok(Some(Fat16DirEntry::from_bytes(&self.scratch[offset..offset + DIR_ENTRY_SIZE])))
+    }
+
+    fn write_dir_entry(&mut self, dir: Dir, index: usize, entry: &Fat16DirEntry) -> Result<(), Fat16Error> {
+        let (lba, offset) = self.dir_entry_location(dir, index)?.ok_or(Fat16Error::Corrupt)?;
+        let bps = self.bytes_per_sector;
+        self.device.read_blocks(lba, &mut self.scratch[..bps])?;
+        put(&mut self.scratch, offset, &entry.to_bytes());
+        self.device.write_blocks(lba, &self.scratch[..bps])?;
+        Ok(self.device.flush()?)
+    }
+
+    // Pierwszy zajęty wpis od `index` razem z nazwą złożoną z fragmentów
+    // LFN; None na końcu używanej części katalogu. Następny wpis szukamy od
+    // `item.index + 1`. Zwraca też `.`, `..` i etykietę woluminu.
+    pub fn next_item(&mut self, dir: Dir, mut index: usize) -> Result<Option<DirItem>, Fat16Error> {
+        let mut lfn = LfnParts::new();
+        while let Some(entry) = self.read_dir_entry(dir, index)? {
+            if entry.name[0] == 0x00 { break; }
+            if entry.is_free() {
+                lfn.reset();
+            } else if entry.is_lfn() {
+                lfn.push(index, &entry);
+            } else {
+                let alias = entry.short_name();
+                let item = match lfn.name(&alias) {
+                    Some(name) => DirItem { entry, name, index, first_slot: lfn.first_slot },
+                    None => DirItem { entry, name: FileName::from(alias), index, first_slot: index },
+                };
+                return Ok(Some(item));
+            }
+            index += 1;
+        }
+        Ok(None)
+    }
+
+    // `count` kolejnych wolnych wpisów (fragmenty LFN muszą leżeć tuż przed
+    // wpisem 8.3). Podkatalog bez takiego miejsca rośnie o wyzerowane klastry
+    // (0x00 w nazwie = koniec katalogu); katalog główny nie może.
+    fn free_dir_slots(&mut self, dir: Dir, count: usize) -> Result<usize, Fat16Error> {
+        let mut run_start = 0;
+        let mut run_len = 0;
+        let mut index = 0;
+        while let Some(entry) = self.read_dir_entry(dir, index)? {
+            if !entry.is_free() {
+                run_len = 0;
+            } else {
+                if run_len == 0 { run_start = index; }
+                run_len += 1;
+                if run_len == count { return Ok(run_start); }
+            }
+            index += 1;
+        }
+        if dir.is_root() {
+            return Err(Fat16Error::DirectoryFull);
+        }
+        // Wolne wpisy na końcu katalogu przechodzą w nowe klastry
+        if run_len == 0 { run_start = index; }
+        let clusters = ((count - run_len) * DIR_ENTRY_SIZE).div_ceil(self.bytes_per_cluster());
+        let last = self.chain_last(dir.0)?.ok_or(Fat16Error::Corrupt)?;
+        let first_new = self.extend_chain(last, clusters)?;
+        let mut cursor = ChainCursor::new(first_new);
+        while let Some(cluster) = cursor.advance(self)? {
+            self.zero_cluster(cluster)?;
+        }
+        Ok(run_start)
+    }
+
+    fn zero_cluster(&mut self, cluster: u16) -> Result<(), Fat16Error> {
+        let bps = self.bytes_per_sector;
+        let lba = self.cluster_lba(cluster);
+        self.scratch[..bps].fill(0);
+        for i in 0..self.sectors_per_cluster as u64 {
+            self.device.write_blocks(lba + i, &self.scratch[..bps])?;
+        }
+        Ok(())
+    }
+
+    fn find_item(&mut self, dir: Dir, matches: impl Fn(&DirItem) -> bool) -> Result<Option<DirItem>, Fat16Error> {
+        let mut index = 0;
+        while let Some(item) = self.next_item(dir, index)? {
+            if matches(&item) { return Ok(Some(item)); }
+            index = item.index + 1;
+        }
+        Ok(None)
+    }
+
+    // Plik lub katalog o nazwie `name` - długiej albo aliasie 8.3, bez
+    // względu na wielkość liter ("notatki.txt", "NOTATKI.TXT", "PROJEC~1.TXT")
+    fn find_entry(&mut self, dir: Dir, name: &FileName) -> Result<Option<DirItem>, Fat16Error> {
+        self.find_item(dir, |item| {
+            !item.entry.is_volume_label()
+                && (item.name.eq_ignore_case(name) || FileName::from(item.entry.short_name()).eq_ignore_case(name))
+        })
+    }
+
+    fn find_alias(&mut self, dir: Dir, alias: &ShortName) -> Result<Option<DirItem>, Fat16Error> {
+        self.find_item(dir, |item| item.entry.short_name() == *alias)
+    }
+
+    // `.` i `..` nie dają się wskazać nazwą (FileName ich nie przyjmuje)
+    fn existing_entry(&mut self, dir: Dir, name: &FileName) -> Result<DirItem, Fat16Error> {
+        self.find_entry(dir, name)?.ok_or(Fat16Error::NotFound)
+    }
+
+    fn existing_file(&mut self, dir: Dir, name: &FileName) -> Result<DirItem, Fat16Error> {
+        let item = self.existing_entry(dir, name)?;
+        if item.entry.is_dir() { return Err(Fat16Error::IsADirectory); }
+        Ok(item)
+    }
+
+    fn ensure_absent(&mut self, dir: Dir, name: &FileName) -> Result<(), Fat16Error> {
+        match self.find_entry(dir, name)? {
+            Some(_) => Err(Fat16Error::AlreadyExists),
+            None => Ok(()),
+        }
+    }
+
+    // Pusty katalog ma tylko `.`, `..` i wpisy usunięte
+    fn dir_is_empty(&mut self, dir: Dir) -> Result<bool, Fat16Error> {
+        let item = self.find_item(dir, |item| !item.entry.short_name().is_dot())?;
+        Ok(item.is_none())
+    }
+
+    // Alias 8.3 dla nowej nazwy: ona sama, gdy jest nazwą 8.3; rdzeń, gdy
+    // różni się od nazwy tylko wielkością liter; inaczej rdzeń z numerem ~n
+    // wolnym w katalogu. `except` to wpis, którego alias można przejąć
+    // (zmiana nazwy).
+    fn alias_for(&mut self, dir: Dir, name: &FileName, except: Option<usize>) -> Result<ShortName, Fat16Error> {
+        if let Some(short) = name.as_short_name() {
+            return Ok(short);
+        }
+        let (base, base_len, ext) = name.alias_basis();
+        let is_free = |volume: &mut Self, alias: &ShortName| -> Result<bool, Fat16Error> {
+            Ok(volume.find_alias(dir, alias)?.is_none_or(|item| Some(item.index) == except))
+        };
+
+        let alias = ShortName { name: base, ext };
+        if name.eq_ignore_case(&FileName::from(alias)) && is_free(self, &alias)? {
+            return Ok(alias);
+        }
+        for n in 1..=MAX_ALIAS_TAIL {
+            let mut digits = [0u8; 7];
+            let mut tail_len = 0;
+            let mut rest = n;
+            while rest > 0 {
+                digits[tail_len] = b'0' + (rest % 10) as u8;
+                tail_len += 1;
+                rest /= 10;
+            }
+            // "PROJECT" + ~12 -> PROJE~12: rdzeń ustępuje miejsca numerowi
+            let keep = base_len.min(8 - 1 - tail_len);
+            let mut alias = ShortName { name: [b' '; 8], ext };
+            alias.name[..keep].copy_from_slice(&base[..keep]);
+            alias.name[keep] = b'~';
+            for (dst, &digit) in alias.name[keep + 1..].iter_mut().zip(digits[..tail_len].iter().rev()) {
+                *dst = digit;
+            }
+            if is_free(self, &alias)? {
+                return Ok(alias);
+            }
+        }
+        Err(Fat16Error::AlreadyExists)
+    }
+
+    // Wyznacza alias i wolne miejsce na nowy wpis. Nic nie zapisuje, poza
+    // ewentualnym powiększeniem podkatalogu.
+    fn place(&mut self, dir: Dir, name: &FileName, except: Option<usize>) -> Result<Placement, Fat16Error> {
+        if name.is_empty() { return Err(Fat16Error::InvalidName); }
+        let alias = self.alias_for(dir, name, except)?;
+        let lfn_count = if name.as_short_name() == Some(alias) { 0 } else { name.len.div_ceil(LFN_CHARS) };
+        let first_slot = self.free_dir_slots(dir, lfn_count + 1)?;
+        Ok(Placement { alias, lfn_count, first_slot })
+    }
+
+    // Zapisuje fragmenty LFN (od ostatniego - ten ma znacznik 0x40) i wpis
+    // 8.3 z aliasem w miejscu wyznaczonym przez `place`
+    fn write_placed(&mut self, dir: Dir, name: &FileName, placement: &Placement, entry: &Fat16DirEntry) -> Result<(), Fat16Error> {
+        let checksum = lfn_checksum(&placement.alias);
+        for i in 0..placement.lfn_count {
+            let seq = placement.lfn_count - i;
+            self.write_dir_entry(dir, placement.first_slot + i, &lfn_entry(name, seq, i == 0, checksum))?;
+        }
+        let mut entry = *entry;
+        entry.name = placement.alias.name;
+        entry.ext = placement.alias.ext;
+        self.write_dir_entry(dir, placement.index(), &entry)
+    }
+
+    // Oznacza jako usunięte (0xE5) wpis 8.3 i jego fragmenty LFN
+    fn erase_item(&mut self, dir: Dir, item: &DirItem) -> Result<(), Fat16Error> {
+        for slot in item.first_slot..item.index {
+            let mut part = self.read_dir_entry(dir, slot)?.ok_or(Fat16Error::Corrupt)?;
+            part.name[0] = DELETED_MARKER;
+            self.write_dir_entry(dir, slot, &part)?;
+        }
+        let mut entry = item.entry;
+        entry.first_cluster = 0;
+        entry.file_size = 0;
+        entry.name[0] = DELETED_MARKER;
+        self.write_dir_entry(dir, item.index, &entry)
+    }
+
+    // --- ŚCIEŻKI ---
+
+    // Katalog nadrzędny według wpisu `..` (katalog główny jest swoim rodzicem)
+    fn parent_dir(&mut self, dir: Dir) -> Result<Dir, Fat16Error> {
+        if dir.is_root() { return Ok(Dir::ROOT); }
+        let item = self.find_alias(dir, &ShortName::DOT_DOT)?.ok_or(Fat16Error::Corrupt)?;
+        Ok(item.entry.dir())
+    }
+
+    fn subdir(&mut self, dir: Dir, name: &FileName) -> Result<Dir, Fat16Error> {
+        let item = self.existing_entry(dir, name)?;
+        if !item.entry.is_dir() { return Err(Fat16Error::NotADirectory); }
+        Ok(item.entry.dir())
+    }
+
+    // Katalog pod ścieżką liczoną od katalogu głównego: "/DOCS/SUB", "DOCS",
+    // "/Moje projekty/.." i "/" (albo pusta) = katalog główny. Separator `/` lub `\`.
+    pub fn open_dir(&mut self, path: &[u8]) -> Result<Dir, Fat16Error> {
+        let mut dir = Dir::ROOT;
+        for part in path.split(|&c| is_path_separator(c)) {
+            dir = match part {
+                b"" | b"." => dir,
+                b".." => self.parent_dir(dir)?,
+                _ => self.subdir(dir, &FileName::parse(part)?)?,
+            };
+        }
+        Ok(dir)
+    }
+
+    // Ścieżka pliku -> katalog + nazwa: "/DOCS/NOTES.TXT" -> (DOCS, NOTES.TXT)
+    pub fn resolve(&mut self, path: &[u8]) -> Result<(Dir, FileName), Fat16Error> {
+        let (dir_path, name) = match path.iter().rposition(|&c| is_path_separator(c)) {
+            Some(slash) => (&path[..slash], &path[slash + 1..]),
+            None => (&[][..], path),
+        };
+        Ok((self.open_dir(dir_path)?, FileName::parse(name)?))
+    }
+
+    // --- OPERACJE NA PLIKACH ---
+    // Nazwy wskazują wpis długą nazwą albo aliasem 8.3; nowe wpisy dostają
+    // fragmenty LFN, gdy nazwa nie jest nazwą 8.3.
+
+    // Zapis pliku: istniejący wpis jest nadpisywany w miejscu, nowy tworzony
+    // tylko gdy pliku jeszcze nie ma
+    pub fn save(&mut self, dir: Dir, name: &FileName, data: &[u8]) -> Result<(), Fat16Error> {
+        // Miejsce na nowy wpis wyznaczamy przed alokacją - przy pełnym
+        // katalogu dane nie trafiają na dysk
+        let (placement, mut entry) = match self.find_entry(dir, name)? {
+            Some(item) if item.entry.is_dir() => return Err(Fat16Error::IsADirectory),
+            Some(item) => {
+                let placement = Placement { alias: item.entry.short_name(), lfn_count: 0, first_slot: item.index };
+                (placement, item.entry)
+            }
+            None => {
+                let placement = self.place(dir, name, None)?;
+                let entry = Fat16DirEntry::new_file(&placement.alias, self.now());
+                (placement, entry)
+            }
+        };
+
+        // Wpis zapisujemy dopiero po udanej alokacji - przy braku miejsca
+        // nie zostaje pusty plik-widmo
+        let clusters = self.clusters_for(data.len());
+        let first_cluster = self.resize_chain(entry.first_cluster, clusters)?;
+        let written = self.write_chain(first_cluster, data);
+
+        // Wpis zapisujemy także po błędzie danych - łańcuch już ma nową długość
+        entry.first_cluster = first_cluster;
+        entry.file_size = data.len() as u32;
+        entry.touch(self.now());
+        self.write_placed(dir, name, &placement, &entry)?;
+        written
+    }
+
+    pub fn load(&mut self, dir: Dir, name: &FileName, buf: &mut [u8]) -> Result<usize, Fat16Error> {
+        let item = self.existing_file(dir, name)?;
+        let read = self.read_chain(item.entry.first_cluster, item.entry.file_size as usize, buf)?;
+
+        // Dzień ostatniego dostępu - zapis tylko z zegarem i gdy dzień się
+        // zmienił; jego błąd nie psuje udanego odczytu
+        let today = (self.clock)().map(|now| FatTime::new(now).date);
+        if let Some(today) = today.filter(|&today| today != item.entry.last_access_date) {
+            let mut entry = item.entry;
+            entry.last_access_date = today;
+            if let Err(error) = self.write_dir_entry(dir, item.index, &entry) {
+                log_warn!("access date not updated: {}", error);
+            }
+        }
+        Ok(read)
+    }
+
+    pub fn create(&mut self, dir: Dir, name: &FileName) -> Result<(), Fat16Error> {
+        // Pusty plik: wpis w katalogu bez klastrów (first_cluster = 0, jak w FAT16)
+        self.ensure_absent(dir, name)?;
+        self.save(dir, name, &[])
+    }
+
+    // Nowy podkatalog z wpisami `.` (on sam) i `..` (rodzic, 0 = katalog główny)
+    pub fn mkdir(&mut self, dir: Dir, name: &FileName) -> Result<Dir, Fat16Error> {
+        self.ensure_absent(dir, name)?;
+        let placement = self.place(dir, name, None)?;
+
+        let cluster = self.alloc_chain(1)?;
+        let new_dir = Dir(cluster);
+        let now = self.now();
+        let filled = self.zero_cluster(cluster)
+            .and_then(|_| self.write_dir_entry(new_dir, 0, &Fat16DirEntry::new_dir(&ShortName::DOT, cluster, now)))
+            .and_then(|_| self.write_dir_entry(new_dir, 1, &Fat16DirEntry::new_dir(&ShortName::DOT_DOT, dir.0, now)));
+        if let Err(error) = filled {
+            self.free_chain(cluster)?;
+            return Err(error);
+        }
+        self.write_placed(dir, name, &placement, &Fat16DirEntry::new_dir(&placement.alias, cluster, now))?;
+        Ok(new_dir)
+    }
+
+    // Skraca plik do `new_size` bajtów i zwalnia nadmiarowe klastry
+    pub fn truncate(&mut self, dir: Dir, name: &FileName, new_size: u32) -> Result<(), Fat16Error> {
+        let item = self.existing_file(dir, name)?;
+        let mut entry = item.entry;
+        if new_size >= entry.file_size { return Ok(()); }
+
+        let keep = self.clusters_for(new_size as usize);
+        let first_cluster = self.truncate_chain(entry.first_cluster, keep)?;
+        entry.first_cluster = first_cluster;
+        entry.file_size = new_size;
+        entry.touch(self.now());
+        self.write_dir_entry(dir, item.index, &entry)
+    }
+
+    // Usuwa plik albo pusty katalog: znacznik 0xE5 we wpisie (i jego
+    // fragmentach LFN) oraz zwolnienie łańcucha klastrów
+    pub fn delete(&mut self, dir: Dir, name: &FileName) -> Result<(), Fat16Error> {
+        let item = self.existing_entry(dir, name)?;
+        if item.entry.is_dir() && !self.dir_is_empty(item.entry.dir())? {
+            return Err(Fat16Error::DirectoryNotEmpty);
+        }
+        self.free_chain(item.entry.first_cluster)?;
+        self.erase_item(dir, &item)
+    }
+
+    // Zmiana nazwy pliku lub katalogu w tym samym katalogu - dane i łańcuch
+    // klastrów zostają na miejscu. Nowa nazwa może potrzebować innej liczby
+    // fragmentów LFN, więc wpis powstaje od nowa, a stary jest usuwany.
+    pub fn rename(&mut self, dir: Dir, from: &FileName, to: &FileName) -> Result<(), Fat16Error> {
+        let item = self.existing_entry(dir, from)?;
+        // Sama zmiana wielkości liter ("notatki.txt" -> "Notatki.txt") trafia na ten sam wpis
+        if self.find_entry(dir, to)?.is_some_and(|other| other.index != item.index) {
+            return Err(Fat16Error::AlreadyExists);
+        }
+        let placement = self.place(dir, to, Some(item.index))?;
+        self.write_placed(dir, to, &placement, &item.entry)?;
+        self.erase_item(dir, &item)
+    }
+
+    // Kopia pliku do nowego wpisu i nowego łańcucha (sektor po sektorze).
+    // Kopia powstaje teraz, ale zachowuje czas modyfikacji oryginału.
+    pub fn copy(&mut self, dir: Dir, from: &FileName, to: &FileName) -> Result<(), Fat16Error> {
+        let source = self.existing_file(dir, from)?.entry;
+        self.ensure_absent(dir, to)?;
+
+        let placement = self.place(dir, to, None)?;
+        let mut dst_first = 0;
+        let clusters = self.clusters_for(source.file_size as usize);
+        if clusters > 0 {
+            dst_first = self.alloc_chain(clusters)?;
+            if let Err(error) = self.copy_chain(source.first_cluster, dst_first) {
+                // Bez wpisu w katalogu nowy łańcuch byłby zgubionymi klastrami
+                self.free_chain(dst_first)?;
+                return Err(error);
+            }
+        }
+
+        let mut entry = Fat16DirEntry::new_file(&placement.alias, self.now());
+        entry.first_cluster = dst_first;
+        entry.file_size = source.file_size;
+        entry.m_date = source.m_date;
+        entry.m_time = source.m_time;
+        self.write_placed(dir, to, &placement, &entry)
+    }
+
+    fn copy_chain(&mut self, src_first: u16, dst_first: u16) -> Result<(), Fat16Error> {
+        let bps = self.bytes_per_sector;
+        let mut src = ChainCursor::new(src_first);
+        let mut dst = ChainCursor::new(dst_first);
+        while let (Some(src_cluster), Some(dst_cluster)) = (src.advance(self)?, dst.advance(self)?) {
+            let (src_lba, dst_lba) = (self.cluster_lba(src_cluster), self.cluster_lba(dst_cluster));
+            for i in 0..self.sectors_per_cluster as u64 {
+                self.device.read_blocks(src_lba + i, &mut self.scratch[..bps])?;
+                self.device.write_blocks(dst_lba + i, &self.scratch[..bps])?;
+            }
+        }
+        Ok(self.device.flush()?)
+    }
+}
+
+// --- SPRAWDZANIE SPÓJNOŚCI (FSCK) ---
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckMode {
+    Report, // Tylko liczy błędy, nic nie zapisuje
+    Repair, // FAT #1 jest wzorcem; złe łańcuchy są obcinane, zgubione klastry zwalniane
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CheckReport {
+    pub files: usize,
+    pub directories: usize,
+    pub fat_mismatches: usize,  // Sektory FAT, w których kopie się różnią
+    pub bad_chains: usize,      // Wpis wolny/uszkodzony/spoza dysku w łańcuchu albo pętla
+    pub cross_links: usize,     // Klaster należący do więcej niż jednego pliku
+    pub size_mismatches: usize, // file_size nie pasuje do długości łańcucha
+    pub duplicate_names: usize,
+    pub lost_clusters: usize,   // Zajęte w FAT, ale nienależące do żadnego pliku
+}
+
+impl CheckReport {
+    pub fn errors(&self) -> usize {
+        self.fat_mismatches + self.bad_chains + self.cross_links
+            + self.size_mismatches + self.duplicate_names + self.lost_clusters
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.errors() == 0
+    }
+}
+
+impl core::fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "{} files, {} directories, {} FAT sector mismatches, {} bad chains, {} cross-links, \
+             {} size mismatches, {} duplicate names, {} lost clusters",
+            self.files, self.directories, self.fat_mismatches, self.bad_chains, self.cross_links,
+            self.size_mismatches, self.duplicate_names, self.lost_clusters
+        )
+    }
+}
+
+// Bitmapa klastrów (8 KB dla największego FAT16)
+struct ClusterMap([u8; (MAX_FAT16_CLUSTERS + 2).div_ceil(8)]);
+
+impl ClusterMap {
+    fn get(&self, cluster: u16) -> bool {
+        self.0[cluster as usize / 8] & (1 << (cluster % 8)) != 0
+    }
+
+    fn set(&mut self, cluster: u16) {
+        self.0[cluster as usize / 8] |= 1 << (cluster % 8);
+    }
+
+    fn clear(&mut self) {
+        self.0.fill(0);
+    }
+}
+
+// Duplikaty nazw szukamy wśród tylu pierwszych wpisów katalogu (tyle ma nasz katalog główny)
+const MAX_CHECKED_NAMES: usize = 512;
+const MAX_CHECKED_DIRS: usize = 256;
+
+// Skrót (FNV-1a) nazwy bez względu na wielkość liter - pełne nazwy
+// wszystkich wpisów katalogu nie zmieściłyby się w pamięci roboczej
+fn name_hash(name: &FileName) -> u32 {
+    name.chars().iter().fold(0x811C_9DC5, |hash: u32, &c| (hash ^ fold_case(c) as u32).wrapping_mul(0x0100_0193))
+}
+
+struct DirQueue {
+    dirs: [Dir; MAX_CHECKED_DIRS],
+    head: usize,
+    tail: usize,
+    overflow: bool, // Nie wszystkie katalogi zmieściły się w kolejce
+}
+
+impl DirQueue {
+    // Kolejka z samym katalogiem głównym
+    fn reset(&mut self) {
+        self.dirs[0] = Dir::ROOT;
+        self.head = 0;
+        self.tail = 1;
+        self.overflow = false;
+    }
+
+    fn push(&mut self, dir: Dir) {
+        if self.tail == MAX_CHECKED_DIRS {
+            self.overflow = true;
+            return;
+        }
+        self.dirs[self.tail] = dir;
+        self.tail += 1;
+    }
+}
+
+// Pamięć robocza fsck (~24 KB) podawana z zewnątrz: na stosie jądra (16 KB)
+// się nie mieści, a w woluminie zajmowałaby miejsce także bez sprawdzania.
+// Jądro trzyma ją w statycznej zmiennej (same zera - .bss).
+pub struct CheckScratch {
+    claimed: ClusterMap, // Klastry plików już sprawdzonych
+    current: ClusterMap, // Klastry bieżącego pliku (wykrywanie pętli)
+    names: [[u8; 11]; MAX_CHECKED_NAMES], // Nazwy 8.3 bieżącego katalogu
+    name_hashes: [u32; MAX_CHECKED_NAMES], // ...i skróty ich długich nazw (`name_hash`)
+    // Katalogi czekające na sprawdzenie (wszerz, od katalogu głównego)
+    pending: DirQueue,
+}
+
+impl CheckScratch {
+    pub const EMPTY: CheckScratch = CheckScratch {
+        claimed: ClusterMap([0; (MAX_FAT16_CLUSTERS + 2).div_ceil(8)]),
+        current: ClusterMap([0; (MAX_FAT16_CLUSTERS + 2).div_ceil(8)]),
+        names: [[0; 11]; MAX_CHECKED_NAMES],
+        name_hashes: [0; MAX_CHECKED_NAMES],
+        pending: DirQueue { dirs: [Dir::ROOT; MAX_CHECKED_DIRS], head: 0, tail: 0, overflow: false },
+    };
+}
+
+impl<D: BlockDevice> Fat16Volume<D> {
+    // Sprawdza zgodność kopii FAT, łańcuchy i rozmiary plików, wspólne
+    // i zgubione klastry oraz powtórzone nazwy w całym drzewie katalogów.
+    // W trybie Repair poprawia wolumin tak, by kolejne sprawdzenie było czyste.
+    pub fn check(&mut self, mode: CheckMode, scratch: &mut CheckScratch) -> Result<CheckReport, Fat16Error> {
+        let repair = mode == CheckMode::Repair;
+        let mut report = CheckReport::default();
+        self.check_fat_copies(repair, &mut report)?;
+
+        scratch.claimed.clear();
+        scratch.pending.reset();
+        while scratch.pending.head < scratch.pending.tail {
+            let dir = scratch.pending.dirs[scratch.pending.head];
+            scratch.pending.head += 1;
+            self.check_dir(dir, repair, scratch, &mut report)?;
+        }
+
+        // Bez pełnego drzewa klastry niesprawdzonych katalogów wyglądałyby na
+        // zgubione - naprawa by je zwolniła
+        if scratch.pending.overflow {
+            log_warn!("check: more than {} directories, lost clusters not checked", MAX_CHECKED_DIRS);
+        } else {
+            self.check_lost_clusters(repair, &scratch.claimed, &mut report)?;
+        }
+        if repair {
+            self.device.flush()?;
+        }
+        Ok(report)
+    }
+
+    // Wpisy jednego katalogu; podkatalogi trafiają do kolejki
+    fn check_dir(
+        &mut self,
+        dir: Dir,
+        repair: bool,
+        scratch: &mut CheckScratch,
+        report: &mut CheckReport,
+    ) -> Result<(), Fat16Error> {
+        let mut name_count = 0;
+
+        let mut index = 0;
+        while let Some(item) = self.next_item(dir, index)? {
+            index = item.index + 1;
+            let mut entry = item.entry;
+            if entry.is_volume_label() || entry.short_name().is_dot() { continue; }
+            if entry.is_dir() { report.directories += 1; } else { report.files += 1; }
+            let mut changed = false;
+            let mut drop_lfn = false;
+
+            // Powtórzony alias 8.3 albo długa nazwa różniąca się tylko wielkością
+            // liter - `find_entry` znalazłby zawsze tylko pierwszy z tych wpisów
+            let mut full_name = [0u8; 11];
+            full_name[..8].copy_from_slice(&entry.name);
+            full_name[8..].copy_from_slice(&entry.ext);
+            let hash = name_hash(&item.name);
+            let same_alias = scratch.names[..name_count].contains(&full_name);
+            let same_name = !same_alias
+                && scratch.name_hashes[..name_count].contains(&hash)
+                && self.has_earlier_name(dir, &item)?;
+            if same_alias || same_name {
+                report.duplicate_names += 1;
+                if repair {
+                    // Drugi wpis traci długą nazwę i dostaje wolny alias FSCK.EXT, FSCK~1.EXT...
+                    let alias = self.fsck_alias(dir, &entry.short_name())?;
+                    entry.name = alias.name;
+                    entry.ext = alias.ext;
+                    full_name[..8].copy_from_slice(&entry.name);
+                    full_name[8..].copy_from_slice(&entry.ext);
+                    changed = true;
+                    drop_lfn = true;
+                }
+            }
+            if name_count < MAX_CHECKED_NAMES {
+                scratch.names[name_count] = full_name;
+                scratch.name_hashes[name_count] = if drop_lfn { name_hash(&FileName::from(entry.short_name())) } else { hash };
+                name_count += 1;
+            }
+
+            // Katalog sprawdzamy tylko raz: zajęty już klaster to pętla w drzewie
+            let first = entry.first_cluster;
+            let new_dir = entry.is_dir() && first >= 2 && first < self.cluster_limit && !scratch.claimed.get(first);
+            if entry.is_dir() && first == 0 {
+                report.bad_chains += 1;
+            } else {
+                scratch.current.clear();
+                changed |= self.check_chain(&mut entry, repair, &mut scratch.claimed, &mut scratch.current, report)?;
+            }
+
+            if entry.is_dir() && entry.first_cluster == 0 {
+                // Katalog bez klastrów nie ma nawet `.` i `..` - usuwamy wpis
+                if repair {
+                    entry.name[0] = DELETED_MARKER;
+                    changed = true;
+                    drop_lfn = true;
+                }
+            } else if new_dir && entry.first_cluster == first {
+                scratch.pending.push(entry.dir());
+            }
+            if drop_lfn {
+                // Fragmenty LFN mają sumę kontrolną starego aliasu - byłyby osierocone
+                for slot in item.first_slot..item.index {
+                    let mut part = self.read_dir_entry(dir, slot)?.ok_or(Fat16Error::Corrupt)?;
+                    part.name[0] = DELETED_MARKER;
+                    self.write_dir_entry(dir, slot, &part)?;
+                }
+            }
+            if changed {
+                self.write_dir_entry(dir, item.index, &entry)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Czy przed `item` leży wpis o tej samej nazwie (bez względu na wielkość
+    // liter). Wołane tylko, gdy zgadza się skrót - zwykle wcale.
+    fn has_earlier_name(&mut self, dir: Dir, item: &DirItem) -> Result<bool, Fat16Error> {
+        let earlier = self.find_item(dir, |other| {
+            other.index < item.index && !other.entry.is_volume_label() && other.name.eq_ignore_case(&item.name)
+        })?;
+        Ok(earlier.is_some())
+    }
+
+    // Alias dla powtórzonej nazwy: rozszerzenie zostaje, rdzeń to FSCK z
+    // numerem ~n wolnym w katalogu (jak dla nowych plików w `alias_for`)
+    fn fsck_alias(&mut self, dir: Dir, old: &ShortName) -> Result<ShortName, Fat16Error> {
+        // Małe litery - `alias_for` nie weźmie tej nazwy wprost jako 8.3,
+        // tylko sprawdzi, czy FSCK.EXT jest wolny
+        let mut chars = [0u16; 8];
+        for (dst, &c) in chars.iter_mut().zip(b"fsck") { *dst = c as u16; }
+        let mut len = 4;
+        let ext_len = old.ext.iter().rposition(|&c| c != b' ').map_or(0, |i| i + 1);
+        if ext_len > 0 {
+            chars[len] = b'.' as u16;
+            for (dst, &c) in chars[len + 1..].iter_mut().zip(&old.ext[..ext_len]) { *dst = c as u16; }
+            len += 1 + ext_len;
+        }
+        let name = FileName::from_ucs2(&chars[..len])?;
+        self.alias_for(dir, &name, None)
+    }
+
+    // Porównuje kolejne kopie FAT z pierwszą, sektor po sektorze
+    fn check_fat_copies(&mut self, repair: bool, report: &mut CheckReport) -> Result<(), Fat16Error> {
+        let bps = self.bytes_per_sector;
+        for sector in 0..self.fat_sectors {
+            self.load_fat_sector(sector)?;
+            for fat in 1..self.fat_count {
+                let lba = self.fat_start + (fat * self.fat_sectors + sector) as u64;
+                self.device.read_blocks(lba, &mut self.scratch[..bps])?;
+                if self.scratch[..bps] != self.fat_cache[..bps] {
+                    report.fat_mismatches += 1;
+                    if repair {
+                        self.device.write_blocks(lba, &self.fat_cache[..bps])?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Idzie po łańcuchu pliku i porównuje jego długość z file_size.
+    // Zwraca true, gdy wpis katalogu trzeba zapisać.
+    fn check_chain(
+        &mut self,
+        entry: &mut Fat16DirEntry,
+        repair: bool,
+        claimed: &mut ClusterMap,
+        current: &mut ClusterMap,
+        report: &mut CheckReport,
+    ) -> Result<bool, Fat16Error> {
+        let mut changed = false;
+        let mut cluster = entry.first_cluster;
+        if cluster != 0 && (cluster < 2 || cluster >= self.cluster_limit) {
+            report.bad_chains += 1;
+            cluster = 0;
+            if repair {
+                entry.first_cluster = 0;
+                changed = true;
+            }
+        }
+
+        let mut count = 0;
+        let mut prev = 0;
+        while cluster != 0 {
+            if current.get(cluster) || claimed.get(cluster) {
+                // Pętla we własnym łańcuchu albo klaster innego pliku - ucinamy
+                if current.get(cluster) { report.bad_chains += 1; } else { report.cross_links += 1; }
+                if repair {
+                    if prev == 0 {
+                        entry.first_cluster = 0;
+                        changed = true;
+                    } else {
+                        self.fat_set(prev, FAT_EOC)?;
+                    }
+                }
+                break;
+            }
+            current.set(cluster);
+            count += 1;
+
+            let next = self.fat_get(cluster)?;
+            if next >= FAT_EOC_MIN { break; }
+            // Wolny, zarezerwowany, uszkodzony (0xFFF7) albo spoza dysku
+            if next < 2 || next >= self.cluster_limit {
+                report.bad_chains += 1;
+                if repair { self.fat_set(cluster, FAT_EOC)?; }
+                break;
+            }
+            prev = cluster;
+            cluster = next;
+        }
+
+        // Katalog ma file_size 0 - jego długość wyznacza sam łańcuch
+        let expected = if entry.is_dir() { count } else { self.clusters_for(entry.file_size as usize) };
+        let mut keep = count;
+        if count != expected {
+            report.size_mismatches += 1;
+            if repair {
+                if count > expected {
+                    // Nadmiarowe klastry wracają do puli wolnych
+                    entry.first_cluster = self.truncate_chain(entry.first_cluster, expected)?;
+                    keep = expected;
+                } else {
+                    // Brakujących danych nie odtworzymy - plik kończy się na łańcuchu
+                    entry.file_size = (count * self.bytes_per_cluster()) as u32;
+                }
+                changed = true;
+            }
+        }
+
+        let mut cursor = ChainCursor::new(entry.first_cluster);
+        for _ in 0..keep {
+            let Some(cluster) = cursor.advance(self)? else { break };
+            claimed.set(cluster);
+        }
+        Ok(changed)
+    }
+
+    // Klastry zajęte w FAT, do których nie prowadzi żaden łańcuch
+    fn check_lost_clusters(&mut self, repair: bool, claimed: &ClusterMap, report: &mut CheckReport) -> Result<(), Fat16Error> {
+        for cluster in 2..self.cluster_limit {
+            let value = self.fat_get(cluster)?;
+            if value == FAT_FREE || value == FAT_BAD || claimed.get(cluster) { continue; }
+            report.lost_clusters += 1;
+            if repair { self.fat_set(cluster, FAT_FREE)?; }
+        }
+        Ok(())
+    }
+}
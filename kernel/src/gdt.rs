@@ -0,0 +1,125 @@
+// =============================================================================
+// GDT + TSS (long mode)
+// =============================================================================
+// Bootloader (boot.asm / multiboot.asm) zostawia nam minimalną GDT bez TSS.
+// Ładujemy własną, bo tylko TSS daje dostęp do IST - osobnego stosu dla
+// wybranych wyjątków. Double fault musi działać nawet wtedy, gdy główny stos
+// (16 KB `stack_bottom`) jest już przepełniony.
+//
+// 0x00 - null
+// 0x08 - kod jądra (64-bit)
+// 0x10 - dane jądra
+// 0x18 - TSS (deskryptor 16-bajtowy)
+// =============================================================================
+
+use core::arch::asm;
+use core::cell::UnsafeCell;
+
+pub const KERNEL_CODE_SELECTOR: u16 = 0x08;
+pub const KERNEL_DATA_SELECTOR: u16 = 0x10;
+pub const TSS_SELECTOR: u16 = 0x18;
+
+// Indeks IST (1-7) używany przez bramkę double fault w IDT
+pub const DOUBLE_FAULT_IST: u8 = 1;
+const IST_STACK_SIZE: usize = 16384;
+
+#[repr(C, packed)]
+struct TaskStateSegment {
+    reserved0: u32,
+    rsp: [u64; 3],
+    reserved1: u64,
+    ist: [u64; 7],
+    reserved2: u64,
+    reserved3: u16,
+    iomap_base: u16,
+}
+
+#[repr(C, align(16))]
+struct IstStack([u8; IST_STACK_SIZE]);
+
+#[repr(C, packed)]
+struct GdtPointer {
+    limit: u16,
+    base: u64,
+}
+
+struct GdtState {
+    gdt: [u64; 5],
+    tss: TaskStateSegment,
+}
+
+struct GdtCell(UnsafeCell<GdtState>);
+unsafe impl Sync for GdtCell {}
+
+// Stos IST osobno od GdtState: same zera trafiają do .bss, a nie do .data
+// (obrazu jądra czytanego przez bootloader). TSS trzyma tylko jego adres.
+struct IstStackCell(UnsafeCell<IstStack>);
+unsafe impl Sync for IstStackCell {}
+
+static DOUBLE_FAULT_STACK: IstStackCell = IstStackCell(UnsafeCell::new(IstStack([0; IST_STACK_SIZE])));
+
+static GDT: GdtCell = GdtCell(UnsafeCell::new(GdtState {
+    gdt: [
+        0,
+        0x00AF9A000000FFFF, // Kod: present, ring 0, L=1
+        0x00CF92000000FFFF, // Dane: present, ring 0, RW
+        0,                  // TSS (niskie 8 bajtów) - uzupełniane w init()
+        0,                  // TSS (wysokie 8 bajtów)
+    ],
+    tss: TaskStateSegment {
+        reserved0: 0,
+        rsp: [0; 3],
+        reserved1: 0,
+        ist: [0; 7],
+        reserved2: 0,
+        reserved3: 0,
+        iomap_base: core::mem::size_of::<TaskStateSegment>() as u16, // Brak bitmapy I/O
+    },
+}));
+
+pub fn init() {
+    let state = unsafe { &mut *GDT.0.get() };
+
+    // Stos rośnie w dół - IST wskazuje na koniec bufora
+    let stack_top = DOUBLE_FAULT_STACK.0.get() as u64 + IST_STACK_SIZE as u64;
+    // TSS jest packed - pole kopiujemy w całości zamiast brać referencję
+    let mut ist = state.tss.ist;
+    ist[(DOUBLE_FAULT_IST - 1) as usize] = stack_top;
+    state.tss.ist = ist;
+
+    let tss_base = &state.tss as *const TaskStateSegment as u64;
+    let tss_limit = (core::mem::size_of::<TaskStateSegment>() - 1) as u64;
+    state.gdt[3] = (tss_limit & 0xFFFF)
+        | ((tss_base & 0xFF_FFFF) << 16)
+        | (0x89 << 40)                      // Present, typ 0x9 = dostępny 64-bit TSS
+        | (((tss_limit >> 16) & 0xF) << 48)
+        | (((tss_base >> 24) & 0xFF) << 56);
+    state.gdt[4] = tss_base >> 32;
+
+    let pointer = GdtPointer {
+        limit: (core::mem::size_of::<[u64; 5]>() - 1) as u16,
+        base: state.gdt.as_ptr() as u64,
+    };
+
+    unsafe {
+        asm!(
+            "lgdt [{ptr}]",
+            // Przeładowanie CS wymaga dalekiego powrotu
+            "push {code}",
+            "lea {tmp}, [rip + 2f]",
+            "push {tmp}",
+            "retfq",
+            "2:",
+            "mov ds, {data:x}",
+            "mov es, {data:x}",
+            "mov ss, {data:x}",
+            "ltr {tss:x}",
+            ptr = in(reg) &pointer,
+            code = in(reg) KERNEL_CODE_SELECTOR as u64,
+            data = in(reg) KERNEL_DATA_SELECTOR as u64,
+            tss = in(reg) TSS_SELECTOR as u64,
+            tmp = out(reg) _,
+            options(preserves_flags)
+        );
+    }
+}
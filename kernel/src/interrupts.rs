@@ -0,0 +1,300 @@
+// =============================================================================
+// IDT - tablica deskryptorów przerwań (long mode)
+// =============================================================================
+// Każdy wektor ma krótki stub w asm, który odkłada (kod błędu, numer wektora)
+// i skacze do wspólnego `isr_common`. Ten zapisuje rejestry ogólne i stan FPU/SSE,
+// a potem woła `interrupt_dispatch` z wskaźnikiem na `InterruptFrame`.
+//
+// Wektory:
+// 0x00-0x1F - wyjątki CPU (double fault na osobnym stosie IST)
+// 0x20-0x2F - IRQ0-15 z remapowanego 8259 PIC
+// =============================================================================
+
+use core::arch::{asm, global_asm};
+use core::cell::UnsafeCell;
+use crate::{crash, gdt, keyboard, pic, time};
+
+const IDT_ENTRIES: usize = 256;
+const GATE_INTERRUPT: u8 = 0x8E; // Present, DPL=0, 64-bit interrupt gate
+
+pub const IRQ_TIMER: u8 = 0;
+pub const IRQ_KEYBOARD: u8 = 1;
+
+// Stos po wejściu do isr_common (od najniższego adresu)
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct InterruptFrame {
+    pub r15: u64,
+    pub r14: u64,
+    pub r13: u64,
+    pub r12: u64,
+    pub r11: u64,
+    pub r10: u64,
+    pub r9: u64,
+    pub r8: u64,
+    pub rbp: u64,
+    pub rdi: u64,
+    pub rsi: u64,
+    pub rdx: u64,
+    pub rcx: u64,
+    pub rbx: u64,
+    pub rax: u64,
+    pub vector: u64,
+    pub error_code: u64,
+    // Odłożone przez CPU
+    pub rip: u64,
+    pub cs: u64,
+    pub rflags: u64,
+    pub rsp: u64,
+    pub ss: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct IdtEntry {
+    offset_low: u16,
+    selector: u16,
+    ist: u8,
+    type_attr: u8,
+    offset_mid: u16,
+    offset_high: u32,
+    reserved: u32,
+}
+
+impl IdtEntry {
+    const fn missing() -> Self {
+        IdtEntry { offset_low: 0, selector: 0, ist: 0, type_attr: 0, offset_mid: 0, offset_high: 0, reserved: 0 }
+    }
+
+    fn new(handler: u64, selector: u16, ist: u8) -> Self {
+        IdtEntry {
+            offset_low: handler as u16,
+            selector,
+            ist,
+            type_attr: GATE_INTERRUPT,
+            offset_mid: (handler >> 16) as u16,
+            offset_high: (handler >> 32) as u32,
+            reserved: 0,
+        }
+    }
+}
+
+#[repr(C, packed)]
+struct IdtPointer {
+    limit: u16,
+    base: u64,
+}
+
+struct IdtCell(UnsafeCell<[IdtEntry; IDT_ENTRIES]>);
+unsafe impl Sync for IdtCell {}
+
+static IDT: IdtCell = IdtCell(UnsafeCell::new([IdtEntry::missing(); IDT_ENTRIES]));
+
+global_asm!(
+    r#"
+    .section .text.isr, "ax"
+
+    /* Wyjątki bez kodu błędu - odkładamy 0, żeby ramka miała stały układ */
+    .macro EXC_STUB num
+    exc_stub_\num:
+        push 0
+        push \num
+        jmp isr_common
+    .endm
+
+    /* Wyjątki, dla których CPU sam odkłada kod błędu */
+    .macro EXC_STUB_ERR num
+    exc_stub_\num:
+        push \num
+        jmp isr_common
+    .endm
+
+    .macro IRQ_STUB num
+    irq_stub_\num:
+        push 0
+        push (0x20 + \num)
+        jmp isr_common
+    .endm
+
+    EXC_STUB 0
+    EXC_STUB 1
+    EXC_STUB 2
+    EXC_STUB 3
+    EXC_STUB 4
+    EXC_STUB 5
+    EXC_STUB 6
+    EXC_STUB 7
+    EXC_STUB_ERR 8
+    EXC_STUB 9
+    EXC_STUB_ERR 10
+    EXC_STUB_ERR 11
+    EXC_STUB_ERR 12
+    EXC_STUB_ERR 13
+    EXC_STUB_ERR 14
+    EXC_STUB 15
+    EXC_STUB 16
+    EXC_STUB_ERR 17
+    EXC_STUB 18
+    EXC_STUB 19
+    EXC_STUB 20
+    EXC_STUB_ERR 21
+    EXC_STUB 22
+    EXC_STUB 23
+    EXC_STUB 24
+    EXC_STUB 25
+    EXC_STUB 26
+    EXC_STUB 27
+    EXC_STUB 28
+    EXC_STUB_ERR 29
+    EXC_STUB_ERR 30
+    EXC_STUB 31
+
+    IRQ_STUB 0
+    IRQ_STUB 1
+    IRQ_STUB 2
+    IRQ_STUB 3
+    IRQ_STUB 4
+    IRQ_STUB 5
+    IRQ_STUB 6
+    IRQ_STUB 7
+    IRQ_STUB 8
+    IRQ_STUB 9
+    IRQ_STUB 10
+    IRQ_STUB 11
+    IRQ_STUB 12
+    IRQ_STUB 13
+    IRQ_STUB 14
+    IRQ_STUB 15
+
+    isr_common:
+        push rax
+        push rbx
+        push rcx
+        push rdx
+        push rsi
+        push rdi
+        push rbp
+        push r8
+        push r9
+        push r10
+        push r11
+        push r12
+        push r13
+        push r14
+        push r15
+        mov rdi, rsp          /* &mut InterruptFrame */
+        sub rsp, 512          /* CPU wyrównał stos do 16 - fxsave tego wymaga */
+        fxsave [rsp]
+        cld
+        call interrupt_dispatch
+        fxrstor [rsp]
+        add rsp, 512
+        pop r15
+        pop r14
+        pop r13
+        pop r12
+        pop r11
+        pop r10
+        pop r9
+        pop r8
+        pop rbp
+        pop rdi
+        pop rsi
+        pop rdx
+        pop rcx
+        pop rbx
+        pop rax
+        add rsp, 16           /* numer wektora + kod błędu */
+        iretq
+
+    .section .rodata.isr, "a"
+    .balign 8
+    .global exception_stub_table
+    exception_stub_table:
+    .quad exc_stub_0, exc_stub_1, exc_stub_2, exc_stub_3
+    .quad exc_stub_4, exc_stub_5, exc_stub_6, exc_stub_7
+    .quad exc_stub_8, exc_stub_9, exc_stub_10, exc_stub_11
+    .quad exc_stub_12, exc_stub_13, exc_stub_14, exc_stub_15
+    .quad exc_stub_16, exc_stub_17, exc_stub_18, exc_stub_19
+    .quad exc_stub_20, exc_stub_21, exc_stub_22, exc_stub_23
+    .quad exc_stub_24, exc_stub_25, exc_stub_26, exc_stub_27
+    .quad exc_stub_28, exc_stub_29, exc_stub_30, exc_stub_31
+    .global irq_stub_table
+    irq_stub_table:
+    .quad irq_stub_0, irq_stub_1, irq_stub_2, irq_stub_3
+    .quad irq_stub_4, irq_stub_5, irq_stub_6, irq_stub_7
+    .quad irq_stub_8, irq_stub_9, irq_stub_10, irq_stub_11
+    .quad irq_stub_12, irq_stub_13, irq_stub_14, irq_stub_15
+    "#
+);
+
+unsafe extern "C" {
+    static exception_stub_table: [u64; 32];
+    static irq_stub_table: [u64; 16];
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn interrupt_dispatch(frame: &mut InterruptFrame) {
+    let vector = frame.vector as u8;
+    if vector < 32 {
+        crash::exception(frame);
+    }
+    if (pic::PIC1_OFFSET..pic::PIC1_OFFSET + 16).contains(&vector) {
+        let irq = vector - pic::PIC1_OFFSET;
+        if pic::is_spurious(irq) { return; }
+        match irq {
+            IRQ_TIMER => time::handle_irq(),
+            IRQ_KEYBOARD => keyboard::handle_irq(),
+            _ => log_warn!("unhandled IRQ{}", irq),
+        }
+        pic::end_of_interrupt(irq);
+    }
+}
+
+pub fn init() {
+    let idt = unsafe { &mut *IDT.0.get() };
+    let selector = gdt::KERNEL_CODE_SELECTOR;
+
+    for vector in 0..32 {
+        let handler = unsafe { exception_stub_table[vector] };
+        let ist = if vector == 8 { gdt::DOUBLE_FAULT_IST } else { 0 };
+        idt[vector] = IdtEntry::new(handler, selector, ist);
+    }
+
+    for irq in 0..16 {
+        let handler = unsafe { irq_stub_table[irq] };
+        idt[pic::PIC1_OFFSET as usize + irq] = IdtEntry::new(handler, selector, 0);
+    }
+
+    let pointer = IdtPointer {
+        limit: (core::mem::size_of::<[IdtEntry; IDT_ENTRIES]>() - 1) as u16,
+        base: idt.as_ptr() as u64,
+    };
+    unsafe { asm!("lidt [{0}]", in(reg) &pointer, options(readonly, nostack, preserves_flags)); }
+
+    pic::init();
+}
+
+#[inline(always)]
+pub fn enable() {
+    unsafe { asm!("sti", options(nomem, nostack)); }
+}
+
+#[inline(always)]
+pub fn disable() {
+    unsafe { asm!("cli", options(nomem, nostack)); }
+}
+
+// Flaga IF w RFLAGS
+pub fn are_enabled() -> bool {
+    let rflags: u64;
+    unsafe { asm!("pushfq", "pop {}", out(reg) rflags, options(nomem, preserves_flags)); }
+    rflags & (1 << 9) != 0
+}
+
+// `sti` opóźnia włączenie przerwań o jedną instrukcję, więc para sti+hlt
+// jest atomowa - przerwanie nie ucieknie między sprawdzeniem a uśpieniem.
+#[inline(always)]
+pub fn enable_and_hlt() {
+    unsafe { asm!("sti", "hlt", options(nomem, nostack)); }
+}
@@ -0,0 +1,45 @@
+// =============================================================================
+// PORTY I/O - cienkie wrappery na instrukcje IN/OUT
+// =============================================================================
+
+use core::arch::asm;
+
+/// # Safety
+/// Odczyt może mieć skutki uboczne (np. zdejmuje bajt z bufora klawiatury) -
+/// port musi należeć do urządzenia, którym zarządza wywołujący.
+#[inline(always)]
+pub unsafe fn inb(port: u16) -> u8 {
+    let value: u8;
+    unsafe { asm!("in al, dx", out("al") value, in("dx") port, options(nomem, nostack, preserves_flags)); }
+    value
+}
+
+/// # Safety
+/// Zapis steruje sprzętem (EOI w PIC, komenda ATA) - wywołujący musi znać
+/// urządzenie pod `port` i jego bieżący stan.
+#[inline(always)]
+pub unsafe fn outb(port: u16, value: u8) {
+    unsafe { asm!("out dx, al", in("dx") port, in("al") value, options(nomem, nostack, preserves_flags)); }
+}
+
+/// # Safety
+/// Jak `inb`; 16-bitowy odczyt służy do transferu danych ATA.
+#[inline(always)]
+pub unsafe fn inw(port: u16) -> u16 {
+    let value: u16;
+    unsafe { asm!("in ax, dx", out("ax") value, in("dx") port, options(nomem, nostack, preserves_flags)); }
+    value
+}
+
+/// # Safety
+/// Jak `outb`; 16-bitowy zapis służy do transferu danych ATA.
+#[inline(always)]
+pub unsafe fn outw(port: u16, value: u16) {
+    unsafe { asm!("out dx, ax", in("dx") port, in("ax") value, options(nomem, nostack, preserves_flags)); }
+}
+
+// Krótkie opóźnienie dla wolnych układów (PIC, PIT) - zapis do nieużywanego portu 0x80
+#[inline(always)]
+pub fn io_wait() {
+    unsafe { outb(0x80, 0); }
+}
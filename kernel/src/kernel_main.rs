@@ -1,7 +1,6 @@
 #[warn(unused_imports)]
 use core::arch::asm;
-use crate::macros::main_menu;
-use crate::vga::unicode_menu;
+use crate::{app, fs, gdt, interrupts, keyboard, rtc, serial, time};
 // use crate::vga::vga_clear_hd;
 
 static mut SEED: u64 = 12345;
@@ -34,28 +33,23 @@ pub fn next_rand() -> u64 {
 
 #[unsafe(no_mangle)]
 pub extern "C" fn kernel_main() -> ! {
+    serial::init();
+    serial_println!();
+    log_info!("Rusted kernel booting");
+    gdt::init();
+    interrupts::init(); // Wyjątki CPU obsłużone od tego momentu
+    log_info!("GDT/TSS and IDT loaded, PIC remapped to {:#04x}", crate::pic::PIC1_OFFSET);
     seed_rng();
-    unsafe {
-        
-        asm!("mov byte ptr [0x500], 0"); // Inicjalizacja menu: Main Menu
-        asm!("mov byte ptr [0x509], 0"); // Flaga 720p: 0=wyłączony
-        main_menu();
-
-        asm!("mov byte ptr [0x501], 0"); // Ostatni scancode
-        asm!("mov byte ptr [0x502], 0"); // Rozmiar początkowy 0
-        asm!("mov byte ptr [0x503], 0"); // Flaga blokady animacji (key 9)
-
-        loop { 
-            let status: u8;
-            // Odczyt portu statusu klawiatury (0x64)
-            asm!("in al, 0x64", out("al") status);
-            
-            // Bit 0 informuje, czy w porcie 0x60 są dane do odczytu
-            if status & 0x01 != 0 {
-                poll_keyboard_unified!(0); // Obsługa klawiatury dla wiersza 0
-            }
-            
-            asm!("pause");
-        }
+    match rtc::now() {
+        Some(now) => log_info!("RTC: {}", now),
+        None => log_warn!("RTC not readable, FAT timestamps fall back to 1980-01-01"),
     }
+    fs::fat16::init(); // Montowanie RAMDISK-u (formatowanie przy pierwszym starcie)
+    time::init();
+    keyboard::init();
+    interrupts::enable();
+    log_info!("PIT {} Hz on IRQ0, keyboard on IRQ1, interrupts enabled", time::TICK_HZ);
+
+    // KernelState startuje z wartościami domyślnymi - pierwszy ekran to menu główne
+    app::run()
 }
\ No newline at end of file
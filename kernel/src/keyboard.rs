@@ -0,0 +1,433 @@
+// =============================================================================
+// KLAWIATURA PS/2 - sterownik oparty o IRQ1
+// =============================================================================
+// Handler IRQ1 jedynie odczytuje bajt z portu 0x60 i wrzuca go do bufora
+// pierścieniowego (jeden producent - przerwanie, jeden konsument - pętla UI).
+// Dekodowanie prefiksów (0xE0/0xE1) i bitu zwolnienia odbywa się poza
+// przerwaniem, w `next_event()`. Zamianę zdarzenia na znak lub klawisz
+// (Shift, Caps Lock, Num Lock, Ctrl, Alt/AltGr) robi wspólny `decode()`.
+// =============================================================================
+
+use core::sync::atomic::{AtomicBool, AtomicU8, AtomicU32, AtomicUsize, Ordering};
+use crate::interrupts;
+use crate::io::inb;
+
+const DATA_PORT: u16 = 0x60;
+const STATUS_PORT: u16 = 0x64;
+const BUFFER_SIZE: usize = 128; // Potęga dwójki - indeksy liczone maską
+
+const KEY_LEFT_SHIFT: u8 = 0x2A;
+const KEY_RIGHT_SHIFT: u8 = 0x36;
+const KEY_CTRL: u8 = 0x1D; // Prawy Ctrl to ten sam kod z prefiksem 0xE0
+const KEY_ALT: u8 = 0x38;  // Z prefiksem 0xE0 to AltGr (prawy Alt)
+const KEY_CAPS_LOCK: u8 = 0x3A;
+const KEY_NUM_LOCK: u8 = 0x45;
+
+static BUFFER: [AtomicU8; BUFFER_SIZE] = [const { AtomicU8::new(0) }; BUFFER_SIZE];
+static HEAD: AtomicUsize = AtomicUsize::new(0); // Zapisuje tylko IRQ1
+static TAIL: AtomicUsize = AtomicUsize::new(0); // Zapisuje tylko konsument
+
+// Stan dekodera - używany wyłącznie przez konsumenta
+static EXTENDED_PENDING: AtomicBool = AtomicBool::new(false);
+static PAUSE_SKIP: AtomicU8 = AtomicU8::new(0);
+// Bitmapa wciśniętych klawiszy: bity 0-127 zwykłe, 128-255 z prefiksem 0xE0
+static KEYS_DOWN: [AtomicU32; 8] = [const { AtomicU32::new(0) }; 8];
+// Przełączniki - zmieniane przy każdym wciśnięciu (diody LED nie sterujemy)
+static CAPS_LOCK: AtomicBool = AtomicBool::new(false);
+static NUM_LOCK: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyEvent {
+    pub scancode: u8,   // Kod klawisza bez bitu zwolnienia (0x00-0x7F)
+    pub extended: bool, // Poprzedzony prefiksem 0xE0 (strzałki, Home, Delete...)
+    pub pressed: bool,  // false = break code
+    pub repeat: bool,   // Autorepetycja - klawisz był już wciśnięty
+}
+
+impl KeyEvent {
+    // Surowy kod w zestawie 1 (z bitem 0x80 dla zwolnienia), jak czytany z portu 0x60
+    pub fn raw(&self) -> u8 {
+        if self.pressed { self.scancode } else { self.scancode | 0x80 }
+    }
+}
+
+pub fn init() {
+    // Opróżniamy bufor kontrolera - zaległy bajt blokowałby kolejne zbocze IRQ1
+    unsafe {
+        while inb(STATUS_PORT) & 0x01 != 0 {
+            inb(DATA_PORT);
+        }
+    }
+    crate::pic::unmask(interrupts::IRQ_KEYBOARD);
+}
+
+// Wywoływane z interrupt_dispatch
+pub fn handle_irq() {
+    let scancode = unsafe { inb(DATA_PORT) };
+    let head = HEAD.load(Ordering::Relaxed);
+    let next = (head + 1) & (BUFFER_SIZE - 1);
+    if next == TAIL.load(Ordering::Acquire) {
+        return; // Bufor pełny - gubimy najnowszy bajt zamiast nadpisywać stare
+    }
+    BUFFER[head].store(scancode, Ordering::Relaxed);
+    HEAD.store(next, Ordering::Release);
+}
+
+fn pop_raw() -> Option<u8> {
+    let tail = TAIL.load(Ordering::Relaxed);
+    if tail == HEAD.load(Ordering::Acquire) {
+        return None;
+    }
+    let byte = BUFFER[tail].load(Ordering::Relaxed);
+    TAIL.store((tail + 1) & (BUFFER_SIZE - 1), Ordering::Release);
+    Some(byte)
+}
+
+// Aktualizuje bitmapę i zwraca true, gdy to kolejne wciśnięcie bez zwolnienia
+fn update_key_down(scancode: u8, extended: bool, pressed: bool) -> bool {
+    let index = scancode as usize + if extended { 128 } else { 0 };
+    let word = &KEYS_DOWN[index / 32];
+    let bit = 1u32 << (index % 32);
+    if pressed {
+        word.fetch_or(bit, Ordering::Relaxed) & bit != 0
+    } else {
+        word.fetch_and(!bit, Ordering::Relaxed);
+        false
+    }
+}
+
+// Czy klawisz jest w tej chwili wciśnięty (wg zdarzeń już odebranych przez UI)
+pub fn is_down(scancode: u8, extended: bool) -> bool {
+    let index = scancode as usize + if extended { 128 } else { 0 };
+    KEYS_DOWN[index / 32].load(Ordering::Relaxed) & (1u32 << (index % 32)) != 0
+}
+
+pub fn shift_down() -> bool {
+    is_down(KEY_LEFT_SHIFT, false) || is_down(KEY_RIGHT_SHIFT, false)
+}
+
+pub fn ctrl_down() -> bool {
+    is_down(KEY_CTRL, false) || is_down(KEY_CTRL, true)
+}
+
+pub fn alt_down() -> bool {
+    is_down(KEY_ALT, false)
+}
+
+pub fn altgr_down() -> bool {
+    is_down(KEY_ALT, true)
+}
+
+// =============================================================================
+// DEKODER - zdarzenie klawisza na znak (układ US, polskie litery pod Alt/AltGr)
+// =============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Char(u8),  // Znak do wpisania: ASCII 0x20-0x7E albo polski glif 0x01-0x09
+    Ctrl(u8),  // Ctrl + litera, zawsze mała ('a'-'z')
+    Enter,
+    Tab,
+    Backspace,
+    Escape,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    Delete,
+    F(u8),     // F1-F12
+    None,      // Zwolnienie, modyfikator albo klawisz bez znaczenia
+}
+
+// Znaki bez i z Shiftem dla kodów 0x02-0x35 (0 = brak znaku)
+const PLAIN: &[u8; 0x36] = b"\0\x001234567890-=\0\0qwertyuiop[]\0\0asdfghjkl;'`\0\\zxcvbnm,./";
+const SHIFTED: &[u8; 0x36] = b"\0\0!@#$%^&*()_+\0\0QWERTYUIOP{}\0\0ASDFGHJKL:\"~\0|ZXCVBNM<>?";
+
+// Alt/AltGr + litera -> glify 0x01-0x09 z vga.rs (układ polski programisty)
+fn polish_letter(scancode: u8) -> u8 {
+    match scancode {
+        0x1E => 0x01, // A -> ą
+        0x2E => 0x02, // C -> ć
+        0x12 => 0x03, // E -> ę
+        0x26 => 0x04, // L -> ł
+        0x31 => 0x05, // N -> ń
+        0x18 => 0x06, // O -> ó
+        0x1F => 0x07, // S -> ś
+        0x2D => 0x08, // X -> ź
+        0x2C => 0x09, // Z -> ż
+        _ => 0,
+    }
+}
+
+// Klawisze z prefiksem 0xE0 - blok nawigacyjny, prawy Enter i '/' na klawiaturze numerycznej
+fn decode_extended(scancode: u8) -> Key {
+    match scancode {
+        0x1C => Key::Enter,
+        0x35 => Key::Char(b'/'),
+        0x47 => Key::Home,
+        0x48 => Key::Up,
+        0x49 => Key::PageUp,
+        0x4B => Key::Left,
+        0x4D => Key::Right,
+        0x4F => Key::End,
+        0x50 => Key::Down,
+        0x51 => Key::PageDown,
+        0x52 => Key::Insert,
+        0x53 => Key::Delete,
+        _ => Key::None, // Prawy Ctrl/AltGr, sztuczne Shifty (E0 2A), klawisze Windows
+    }
+}
+
+// Klawiatura numeryczna 0x47-0x53: cyfry przy Num Lock, inaczej nawigacja
+fn decode_keypad(scancode: u8, num_lock: bool) -> Key {
+    const DIGITS: &[u8; 13] = b"789-456+1230.";
+    let digit = DIGITS[(scancode - 0x47) as usize];
+    if digit == b'-' || digit == b'+' || num_lock {
+        return Key::Char(digit);
+    }
+    match scancode {
+        0x52 => Key::Insert,
+        0x53 => Key::Delete,
+        0x4C => Key::None, // Środkowe 5 bez Num Lock
+        _ => decode_extended(scancode),
+    }
+}
+
+// Modyfikatory i przełączniki w chwili dekodowania
+#[derive(Clone, Copy, Default)]
+struct Modifiers {
+    shift: bool,
+    ctrl: bool,
+    alt: bool, // Lewy Alt albo AltGr - oba dają polskie litery
+    caps_lock: bool,
+    num_lock: bool,
+}
+
+impl Modifiers {
+    fn current() -> Self {
+        Modifiers {
+            shift: shift_down(),
+            ctrl: ctrl_down(),
+            alt: alt_down() || altgr_down(),
+            caps_lock: CAPS_LOCK.load(Ordering::Relaxed),
+            num_lock: NUM_LOCK.load(Ordering::Relaxed),
+        }
+    }
+}
+
+// Zamienia wciśnięcie na klawisz logiczny; zwolnienia dają Key::None
+pub fn decode(event: &KeyEvent) -> Key {
+    decode_with(event, Modifiers::current())
+}
+
+fn decode_with(event: &KeyEvent, modifiers: Modifiers) -> Key {
+    if !event.pressed { return Key::None; }
+    let scancode = event.scancode;
+    if event.extended { return decode_extended(scancode); }
+
+    match scancode {
+        0x01 => return Key::Escape,
+        0x0E => return Key::Backspace,
+        0x0F => return Key::Tab,
+        0x1C => return Key::Enter,
+        0x37 => return Key::Char(b'*'), // '*' na klawiaturze numerycznej
+        0x39 => return Key::Char(b' '),
+        0x3B..=0x44 => return Key::F(scancode - 0x3A),
+        0x57 | 0x58 => return Key::F(scancode - 0x4C),
+        0x47..=0x53 => return decode_keypad(scancode, modifiers.num_lock),
+        _ => {}
+    }
+    if scancode as usize >= PLAIN.len() || PLAIN[scancode as usize] == 0 {
+        return Key::None;
+    }
+
+    let plain = PLAIN[scancode as usize];
+    if modifiers.alt {
+        let glyph = polish_letter(scancode);
+        return if glyph != 0 { Key::Char(glyph) } else { Key::None };
+    }
+    if modifiers.ctrl {
+        return if plain.is_ascii_lowercase() { Key::Ctrl(plain) } else { Key::None };
+    }
+    // Caps Lock odwraca Shift tylko dla liter
+    let mut shift = modifiers.shift;
+    if plain.is_ascii_lowercase() && modifiers.caps_lock {
+        shift = !shift;
+    }
+    Key::Char(if shift { SHIFTED[scancode as usize] } else { plain })
+}
+
+// Zwraca następne zdarzenie klawiatury lub None, gdy bufor jest pusty
+pub fn next_event() -> Option<KeyEvent> {
+    while let Some(byte) = pop_raw() {
+        // Pause/Break: E1 1D 45 E1 9D C5 - ignorujemy całą sekwencję
+        let skip = PAUSE_SKIP.load(Ordering::Relaxed);
+        if skip > 0 {
+            PAUSE_SKIP.store(skip - 1, Ordering::Relaxed);
+            continue;
+        }
+        match byte {
+            0xE0 => { EXTENDED_PENDING.store(true, Ordering::Relaxed); }
+            0xE1 => { PAUSE_SKIP.store(5, Ordering::Relaxed); }
+            0x00 | 0xFA | 0xFE | 0xFF => {} // ACK/resend/błędy kontrolera
+            _ => {
+                let scancode = byte & 0x7F;
+                let extended = EXTENDED_PENDING.swap(false, Ordering::Relaxed);
+                let pressed = byte & 0x80 == 0;
+                let repeat = update_key_down(scancode, extended, pressed);
+                if pressed && !repeat && !extended {
+                    match scancode {
+                        KEY_CAPS_LOCK => { CAPS_LOCK.fetch_xor(true, Ordering::Relaxed); }
+                        KEY_NUM_LOCK => { NUM_LOCK.fetch_xor(true, Ordering::Relaxed); }
+                        _ => {}
+                    }
+                }
+                return Some(KeyEvent { scancode, extended, pressed, repeat });
+            }
+        }
+    }
+    None
+}
+
+// Blokuje (hlt) do nadejścia kolejnego zdarzenia
+pub fn wait_event() -> KeyEvent {
+    loop {
+        interrupts::disable();
+        if let Some(event) = next_event() {
+            interrupts::enable();
+            return event;
+        }
+        interrupts::enable_and_hlt();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(scancode: u8) -> KeyEvent {
+        KeyEvent { scancode, extended: false, pressed: true, repeat: false }
+    }
+
+    fn press_extended(scancode: u8) -> KeyEvent {
+        KeyEvent { extended: true, ..press(scancode) }
+    }
+
+    fn plain() -> Modifiers {
+        Modifiers::default()
+    }
+
+    #[test]
+    fn shift_and_caps_lock() {
+        let shift = Modifiers { shift: true, ..plain() };
+        let caps = Modifiers { caps_lock: true, ..plain() };
+        let both = Modifiers { shift: true, caps_lock: true, ..plain() };
+        // (kod, bez niczego, Shift, Caps Lock, Shift + Caps Lock)
+        let cases = [
+            (0x1E, b'a', b'A', b'A', b'a'), // Litery - Caps Lock odwraca Shift
+            (0x2C, b'z', b'Z', b'Z', b'z'),
+            (0x02, b'1', b'!', b'1', b'!'), // Cyfry i symbole - Caps Lock bez wpływu
+            (0x0C, b'-', b'_', b'-', b'_'),
+            (0x28, b'\'', b'"', b'\'', b'"'),
+            (0x2B, b'\\', b'|', b'\\', b'|'),
+            (0x35, b'/', b'?', b'/', b'?'),
+        ];
+        for (scancode, expected_plain, expected_shift, expected_caps, expected_both) in cases {
+            let event = press(scancode);
+            assert_eq!(decode_with(&event, plain()), Key::Char(expected_plain), "{:#x}", scancode);
+            assert_eq!(decode_with(&event, shift), Key::Char(expected_shift), "{:#x} + Shift", scancode);
+            assert_eq!(decode_with(&event, caps), Key::Char(expected_caps), "{:#x} + Caps Lock", scancode);
+            assert_eq!(decode_with(&event, both), Key::Char(expected_both), "{:#x} + oba", scancode);
+        }
+        // Spacja, Tab i Enter nie zależą od Shift
+        assert_eq!(decode_with(&press(0x39), shift), Key::Char(b' '));
+        assert_eq!(decode_with(&press(0x0F), shift), Key::Tab);
+        assert_eq!(decode_with(&press(0x1C), both), Key::Enter);
+    }
+
+    #[test]
+    fn alt_gives_polish_glyphs() {
+        let alt = Modifiers { alt: true, ..plain() };
+        let letters = [(0x1E, 0x01), (0x2E, 0x02), (0x12, 0x03), (0x26, 0x04), (0x31, 0x05),
+                       (0x18, 0x06), (0x1F, 0x07), (0x2D, 0x08), (0x2C, 0x09)];
+        for (scancode, glyph) in letters {
+            assert_eq!(decode_with(&press(scancode), alt), Key::Char(glyph), "{:#x}", scancode);
+            // Shift i Caps Lock nie zmieniają glifu (font ma tylko małe litery)
+            let shifted = Modifiers { shift: true, caps_lock: true, ..alt };
+            assert_eq!(decode_with(&press(scancode), shifted), Key::Char(glyph));
+        }
+        // Alt z literą bez polskiego odpowiednika i z cyfrą nic nie wpisuje
+        assert_eq!(decode_with(&press(0x30), alt), Key::None); // B
+        assert_eq!(decode_with(&press(0x02), alt), Key::None); // 1
+        // Klawisze funkcyjne działają także z Altem
+        assert_eq!(decode_with(&press(0x3D), alt), Key::F(3));
+    }
+
+    #[test]
+    fn ctrl_gives_lowercase_letters() {
+        let ctrl = Modifiers { ctrl: true, ..plain() };
+        assert_eq!(decode_with(&press(0x2C), ctrl), Key::Ctrl(b'z'));
+        let shifted = Modifiers { shift: true, caps_lock: true, ..ctrl };
+        assert_eq!(decode_with(&press(0x15), shifted), Key::Ctrl(b'y'));
+        assert_eq!(decode_with(&press(0x02), ctrl), Key::None);
+    }
+
+    #[test]
+    fn keypad_follows_num_lock() {
+        let num = Modifiers { num_lock: true, ..plain() };
+        let keys = [
+            (0x47, b'7', Key::Home), (0x48, b'8', Key::Up), (0x49, b'9', Key::PageUp),
+            (0x4B, b'4', Key::Left), (0x4C, b'5', Key::None), (0x4D, b'6', Key::Right),
+            (0x4F, b'1', Key::End), (0x50, b'2', Key::Down), (0x51, b'3', Key::PageDown),
+            (0x52, b'0', Key::Insert), (0x53, b'.', Key::Delete),
+        ];
+        for (scancode, digit, navigation) in keys {
+            assert_eq!(decode_with(&press(scancode), num), Key::Char(digit), "{:#x}", scancode);
+            assert_eq!(decode_with(&press(scancode), plain()), navigation, "{:#x}", scancode);
+        }
+        // '-', '+' i '*' nie zależą od Num Lock
+        for modifiers in [plain(), num] {
+            assert_eq!(decode_with(&press(0x4A), modifiers), Key::Char(b'-'));
+            assert_eq!(decode_with(&press(0x4E), modifiers), Key::Char(b'+'));
+            assert_eq!(decode_with(&press(0x37), modifiers), Key::Char(b'*'));
+        }
+    }
+
+    #[test]
+    fn extended_keys_ignore_num_lock_and_shift() {
+        let all = Modifiers { shift: true, num_lock: true, caps_lock: true, ..plain() };
+        let keys = [
+            (0x47, Key::Home), (0x48, Key::Up), (0x49, Key::PageUp), (0x4B, Key::Left),
+            (0x4D, Key::Right), (0x4F, Key::End), (0x50, Key::Down), (0x51, Key::PageDown),
+            (0x52, Key::Insert), (0x53, Key::Delete),
+            (0x1C, Key::Enter),     // Enter na klawiaturze numerycznej
+            (0x35, Key::Char(b'/')), // '/' na klawiaturze numerycznej
+            (0x1D, Key::None),      // Prawy Ctrl
+            (0x38, Key::None),      // AltGr
+            (0x2A, Key::None),      // Sztuczny Shift przed strzałką przy Num Lock
+        ];
+        for (scancode, key) in keys {
+            assert_eq!(decode_with(&press_extended(scancode), plain()), key, "E0 {:#x}", scancode);
+            assert_eq!(decode_with(&press_extended(scancode), all), key, "E0 {:#x} z modyfikatorami", scancode);
+        }
+    }
+
+    #[test]
+    fn releases_and_unknown_keys_decode_to_none() {
+        let release = KeyEvent { pressed: false, ..press(0x1E) };
+        assert_eq!(decode_with(&release, plain()), Key::None);
+        assert_eq!(decode_with(&KeyEvent { pressed: false, ..press_extended(0x48) }, plain()), Key::None);
+        // Modyfikatory same w sobie
+        for scancode in [KEY_LEFT_SHIFT, KEY_RIGHT_SHIFT, KEY_CTRL, KEY_ALT, KEY_CAPS_LOCK] {
+            assert_eq!(decode_with(&press(scancode), plain()), Key::None, "{:#x}", scancode);
+        }
+        assert_eq!(decode_with(&press(0x3B), plain()), Key::F(1));
+        assert_eq!(decode_with(&press(0x44), plain()), Key::F(10));
+        assert_eq!(decode_with(&press(0x58), plain()), Key::F(12));
+    }
+}
@@ -1,26 +1,36 @@
-#![no_std]
+// Testy jednostkowe (`cargo test`) budują bibliotekę na hoście ze std
+#![cfg_attr(not(test), no_std)]
 #![allow(unused_unsafe)]
 
-use core::panic::PanicInfo;
-use core::arch::asm;
-
 #[macro_use]
 pub mod macros;
 pub mod vga;
+#[cfg(not(test))] // Na hoście mem* daje libc
 pub mod shims;
 pub mod kernel_main;
 pub mod fs;
 pub mod editor;
+pub mod io;
+pub mod serial;
+pub mod log;
+pub mod state;
+pub mod gdt;
+pub mod pic;
+pub mod interrupts;
+pub mod keyboard;
+pub mod pit;
+pub mod time;
+pub mod rtc;
+pub mod ata;
+pub mod crash;
+pub mod app;
+pub mod menu;
+pub mod mc;
 
 pub use crate::kernel_main::kernel_main;
 
+#[cfg(not(test))]
 #[panic_handler]
-fn panic(_info: &PanicInfo) -> ! {
-    unsafe {
-        vga_write!(0, 0, b'E', 0x4F);
-        vga_write!(1, 0, b'R', 0x4F);
-        vga_write!(2, 0, b'2', 0x4F);
-        vga_write!(3, 0, b'5', 0x4F);
-        loop { asm!("hlt"); }
-    }
+fn panic(info: &core::panic::PanicInfo) -> ! {
+    crate::crash::panic(info)
 }
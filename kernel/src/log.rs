@@ -0,0 +1,73 @@
+// =============================================================================
+// LOG JĄDRA - poziomy error/warn/info/debug/trace
+// =============================================================================
+// Wpisy trafiają na COM1 w formacie `[sekundy.ms] [LEVEL] moduł: wiadomość`
+// (czas od startu PIT). Z feature `log-vga` są dodatkowo powielane
+// w wierszu 23 ekranu tekstowego.
+// Użycie: log_info!("fat16: {} plikow", n);
+// =============================================================================
+
+use core::fmt;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+    Trace = 5,
+}
+
+impl Level {
+    fn label(self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN ",
+            Level::Info => "INFO ",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+}
+
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(Level::Debug as u8);
+
+#[allow(dead_code)]
+pub fn set_level(level: Level) {
+    MAX_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= MAX_LEVEL.load(Ordering::Relaxed)
+}
+
+// Wywoływane przez makra log_*!
+pub fn _log(level: Level, module: &str, args: fmt::Arguments) {
+    if !enabled(level) { return; }
+    // Pomijamy prefiks nazwy crate ("kernel::fs::fat16" -> "fs::fat16")
+    let module = module.split_once("::").map(|(_, rest)| rest).unwrap_or(module);
+    let uptime = crate::time::uptime();
+    crate::serial::_print(format_args!(
+        "[{:5}.{:03}] [{}] {}: {}\n",
+        uptime.as_secs(), uptime.subsec_millis(), level.label(), module, args
+    ));
+
+    #[cfg(feature = "log-vga")]
+    vga_mirror(level, args);
+}
+
+#[cfg(feature = "log-vga")]
+fn vga_mirror(level: Level, args: fmt::Arguments) {
+    use core::fmt::Write;
+
+    let mut line = crate::vga::LineBuf::new();
+    let _ = write!(line, "[{}] {}", level.label(), args);
+    let color = match level {
+        Level::Error => 0x4F,
+        Level::Warn => 0x0E,
+        _ => 0x07,
+    };
+    vga_print!(0, 23, color, line.padded());
+}
@@ -3,7 +3,7 @@
 #![allow(unused_unsafe)]
 
 use core::panic::PanicInfo;
-use core::arch::{asm,global_asm};
+use core::arch::global_asm;
 
 mod shims;
 #[macro_use]
@@ -11,6 +11,22 @@ mod macros;
 mod vga;
 mod fs;
 mod editor;
+mod io;
+mod serial;
+mod log;
+mod state;
+mod gdt;
+mod pic;
+mod interrupts;
+mod keyboard;
+mod pit;
+mod time;
+mod rtc;
+mod ata;
+mod crash;
+mod app;
+mod menu;
+mod mc;
 pub mod kernel_main;
 
 
@@ -19,7 +35,16 @@ global_asm!(
     .section .text._start
     .global _start
     _start:
+        /* Bootloader wczytuje tylko obraz (.text/.rodata/.data) - .bss zerujemy
+           sami, zanim cokolwiek (łącznie ze stosem) z niej skorzysta */
+        lea rdi, [__bss_start]
+        lea rcx, [__bss_end]
+        sub rcx, rdi
+        xor eax, eax
+        cld
+        rep stosb
         lea rsp, [stack_top]
+        xor ebp, ebp          /* koniec łańcucha ramek dla backtrace */
         call kernel_main      /* call jest bezpieczniejsze niż jmp dla wyrównania stosu */
         
     .section .bss
@@ -31,12 +56,6 @@ global_asm!(
 );
 
 #[panic_handler]
-fn panic(_info: &PanicInfo) -> ! {
-    unsafe {
-        vga_write!(0, 0, b'E', 0x4F);
-        vga_write!(1, 0, b'R', 0x4F);
-        vga_write!(2, 0, b'2', 0x4F);
-        vga_write!(3, 0, b'5', 0x4F);
-        loop { asm!("hlt"); }
-    }
-}
\ No newline at end of file
+fn panic(info: &PanicInfo) -> ! {
+    crate::crash::panic(info)
+}
@@ -0,0 +1,324 @@
+// =============================================================================
+// FAT16 MINI-COMMANDER (MC) - przeglądarka katalogów
+// =============================================================================
+// Klawisze w stylu Norton/Midnight Commandera:
+// ENTER otwiera plik w edytorze albo wchodzi do katalogu (`..` = wyżej),
+// Shift+F4 nowy plik, F6 zmiana nazwy, F7 nowy katalog, F8 usunięcie
+// (z potwierdzeniem), Shift+F5 kopia (samo F5 to globalny reboot),
+// F9 sprawdzenie i naprawa woluminu.
+// =============================================================================
+
+use crate::app::{Action, App, AppId};
+use crate::editor;
+use crate::fs::fat16::{self, CheckMode, Dir, DirItem, FileName};
+use crate::keyboard::{self, KeyEvent};
+use crate::menu;
+use crate::state::{self, InputContext};
+use crate::vga::LineBuf;
+use core::fmt::Write;
+
+const LIST_ROWS: usize = 15;  // Widoczne wiersze; dłuższa lista się przewija
+const NAME_WIDTH: usize = 40; // Dłuższa nazwa traci środek ("Bardzo dl~ga.txt")
+const DATE_COL: u64 = 53;     // Data i czas modyfikacji "20.10.2026 13:30"
+const ROW_WIDTH: usize = 65;  // Nazwa, <DIR>, data - cały wiersz w kolorze zaznaczenia
+const MAX_PATH: usize = 256;  // "/DOCS/Moje projekty" - bieżący katalog w nagłówku
+const PATH_WIDTH: usize = 52; // Tyle ścieżki mieści nagłówek (od kolumny 25)
+
+const KEY_ESC: u8 = 0x01;
+const KEY_ENTER: u8 = 0x1C;
+const KEY_F4: u8 = 0x3E;
+const KEY_F5: u8 = 0x3F;
+const KEY_F6: u8 = 0x40;
+const KEY_F7: u8 = 0x41;
+const KEY_F8: u8 = 0x42;
+const KEY_F9: u8 = 0x43;
+const KEY_UP: u8 = 0x48;
+const KEY_DOWN: u8 = 0x50;
+const KEY_T: u8 = 0x14;
+const KEY_Y: u8 = 0x15;
+
+pub struct MiniCommander {
+    selection: usize,
+    offset: usize,                     // Pierwszy widoczny wpis listy
+    file_count: usize,
+    dir: Dir,                          // Wyświetlany katalog
+    path: [u8; MAX_PATH],              // Jego ścieżka bez końcowego `/` (pusta = katalog główny)
+    path_len: usize,
+    confirm_delete: Option<FileName>,  // Plik czekający na potwierdzenie F8
+    message_open: bool,                // Okno z komunikatem - dowolny klawisz zamyka
+}
+
+impl Default for MiniCommander {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MiniCommander {
+    pub const fn new() -> Self {
+        MiniCommander {
+            selection: 0,
+            offset: 0,
+            file_count: 0,
+            dir: Dir::ROOT,
+            path: [0; MAX_PATH],
+            path_len: 0,
+            confirm_delete: None,
+            message_open: false,
+        }
+    }
+
+    // Wpisy widoczne na liście: zajęte i nieusunięte (0xE5 i fragmenty LFN
+    // pomija `fat16_next_item`), bez etykiety woluminu i bez `.`; `..`
+    // zostaje jako droga do katalogu wyżej
+    fn visible_entries(dir: Dir) -> impl Iterator<Item = DirItem> {
+        let mut index = 0;
+        core::iter::from_fn(move || {
+            let item = fat16::fat16_next_item(dir, index).ok().flatten()?; // Do końca używanej części katalogu
+            index = item.index + 1;
+            Some(item)
+        })
+        .filter(|item| !item.entry.is_volume_label() && item.entry.name != *b".       ")
+    }
+
+    // n-ty widoczny wpis (licząc od początku katalogu, nie od okna listy)
+    fn entry_at(&self, index: usize) -> Option<DirItem> {
+        Self::visible_entries(self.dir).nth(index)
+    }
+
+    fn refresh_list(&mut self) {
+        // Zapamiętaj liczbę plików; zaznaczenie nie może wyjść za listę
+        // (np. po usunięciu ostatniego pliku)
+        self.file_count = Self::visible_entries(self.dir).count();
+        if self.selection >= self.file_count {
+            self.selection = self.file_count.saturating_sub(1);
+        }
+        // Okno listy idzie za zaznaczeniem
+        if self.selection < self.offset {
+            self.offset = self.selection;
+        } else if self.selection >= self.offset + LIST_ROWS {
+            self.offset = self.selection + 1 - LIST_ROWS;
+        }
+
+        let mut rows = Self::visible_entries(self.dir).skip(self.offset);
+        for row in 0..LIST_ROWS {
+            // Wiersze za końcem listy czyścimy - po przewinięciu albo usunięciu
+            // zostałyby na nich stare nazwy
+            let Some(item) = rows.next() else {
+                vga_print!(4, 4 + row as u64, 0x1F, &[b' '; ROW_WIDTH]);
+                continue;
+            };
+            let color = if self.offset + row == self.selection { 0x70 } else { 0x1F }; // Inwersja dla zaznaczenia
+
+            // Nazwa długa (albo 8.3, gdy pliku nie zapisano z LFN); wiersz jest
+            // dopełniony spacjami, żeby zaznaczenie miało stałą szerokość
+            let (text, len) = item.name.fitted_text(NAME_WIDTH);
+            let name = core::str::from_utf8(&text[..len]).unwrap_or("?"); // `text` jest w ASCII
+            let kind = if item.entry.is_dir() { "<DIR>" } else { "" };
+            let mut line = LineBuf::new();
+            let _ = write!(line, "{:<name_width$}  {:<5}  ", name, kind, name_width = NAME_WIDTH);
+            // Wpisy bez znacznika czasu (data 0) mają pustą kolumnę
+            if let Some(time) = item.entry.modified() {
+                let _ = write!(line, "{:02}.{:02}.{:04} {:02}:{:02}", time.day, time.month, time.year, time.hour, time.minute);
+            }
+            vga_print!(4, 4 + row as u64, color, &line.padded()[..ROW_WIDTH]);
+        }
+
+        // Stopka
+        if self.file_count == 0 {
+            vga_print!(2, 22, 0x0E, b" Katalog pusty. [Sh+F4] Plik  [F7] Katalog  [ESC] Wyjdz ");
+        } else {
+            vga_print!(2, 22, 0x0F, b" [Sh+F4] Plik [Sh+F5] Kopia [F6] Nazwa [F7] Katalog [F8] Usun [F9] Fsck [ESC]");
+        }
+    }
+
+    // ENTER na katalogu: `..` ucina ostatni element ścieżki, inny go dokleja.
+    // Katalog bierzemy wprost z wpisu (`..` wskazuje rodzica), więc ścieżka
+    // służy tylko do wyświetlania - nazwy spoza ASCII mają w niej `?`.
+    fn change_dir(&mut self, item: &DirItem) -> Action {
+        if item.entry.short_name().is_dot() {
+            self.path_len = self.path[..self.path_len].iter().rposition(|&c| c == b'/').unwrap_or(0);
+        } else {
+            let (text, len) = item.name.text();
+            if self.path_len + 1 + len > MAX_PATH {
+                return menu::show_message(b" OTWORZ KATALOG ", b"Sciezka jest za dluga");
+            }
+            self.path[self.path_len] = b'/';
+            self.path[self.path_len + 1..self.path_len + 1 + len].copy_from_slice(&text[..len]);
+            self.path_len += 1 + len;
+        }
+
+        self.dir = item.entry.dir();
+        self.selection = 0;
+        self.offset = 0;
+        self.draw();
+        self.refresh_list();
+        Action::None
+    }
+
+    fn dialog(title: &[u8], prompt: &[u8]) {
+        vga_draw_rect!(9, 7, 62, 10, 0x07); // szara ramka
+        vga_draw_rect!(10, 8, 60, 8, 0x01); // granatowe wnętrze
+        vga_print!(12, 9, 0x1F, title);
+        vga_print!(12, 11, 0x1F, prompt);
+    }
+
+    // Okno dialogowe + pasek INPUT na nazwę pliku
+    fn ask_name(title: &[u8], context: InputContext) -> Action {
+        Self::dialog(title, b"Nazwa (np. NOTATKI.TXT, MOJE NOTATKI Z FIZYKI.TXT):");
+        state::with(|s| {
+            s.input_context = context;
+            s.input_len = 0;
+        });
+        Action::Push(AppId::Input)
+    }
+
+    // Odpowiedź na pytanie "Usunac plik?" - T/Y potwierdza, reszta anuluje
+    fn answer_delete(&mut self, name: FileName, key: KeyEvent) -> Action {
+        if (key.scancode == KEY_T || key.scancode == KEY_Y)
+            && let Err(error) = fat16::fat16_delete(self.dir, &name)
+        {
+            return menu::show_error(b" USUN ", error);
+        }
+        self.draw();
+        self.refresh_list();
+        Action::None
+    }
+
+    // F9 - fsck w trybie naprawy; okno z tym, co znaleziono i poprawiono
+    fn check_volume(&mut self) {
+        Self::dialog(b" SPRAWDZANIE WOLUMINU ", b"Sprawdzanie...");
+        let report = match fat16::check(CheckMode::Repair) {
+            Ok(report) => report,
+            Err(error) => {
+                Self::dialog(b" SPRAWDZANIE WOLUMINU ", error.message());
+                self.message_open = true;
+                return;
+            }
+        };
+
+        let status: &[u8] = if report.is_clean() { b"Wolumin spojny" } else { b"Naprawiono:" };
+        Self::dialog(b" SPRAWDZANIE WOLUMINU ", status);
+        let lines = [
+            ("Pliki", report.files, "Katalogi", report.directories),
+            ("Rozne kopie FAT", report.fat_mismatches, "Zle lancuchy", report.bad_chains),
+            ("Wspolne klastry", report.cross_links, "Zly rozmiar", report.size_mismatches),
+            ("Powtorzone nazwy", report.duplicate_names, "Zgubione klastry", report.lost_clusters),
+        ];
+        for (row, (left, a, right, b)) in lines.into_iter().enumerate() {
+            let mut line = LineBuf::new();
+            let _ = write!(line, "{:<17}{:>5}   {:<17}{:>5}", left, a, right, b);
+            vga_print!(12, 12 + row as u64, 0x1F, line.as_bytes());
+        }
+        self.message_open = true;
+    }
+}
+
+impl App for MiniCommander {
+    fn on_enter(&mut self) {
+        self.draw();
+        self.refresh_list();
+    }
+
+    fn on_exit(&mut self) {
+        // Następne otwarcie zaczyna od pierwszego pliku katalogu głównego
+        self.selection = 0;
+        self.offset = 0;
+        self.dir = Dir::ROOT;
+        self.path_len = 0;
+        self.confirm_delete = None;
+        self.message_open = false;
+    }
+
+    fn draw(&self) {
+        vga_clear!(0x00);
+        vga_draw_rect!(1, 1, 78, 22, 0x01); // Ramka tła (granatowa)
+        vga_print!(2, 1, 0x1F, b" FAT16 MINI-COMMANDER ");
+        // Za długa ścieżka: widać jej koniec, czyli bieżący katalog
+        let path: &[u8] = if self.path_len == 0 { b"/" } else { &self.path[..self.path_len] };
+        vga_print!(25, 1, 0x1E, &path[path.len().saturating_sub(PATH_WIDTH)..]);
+        vga_print!(4, 3, 0x17, b"Nazwa");
+        vga_print!(DATE_COL, 3, 0x17, b"Zmodyfikowano");
+        vga_print!(2, 22, 0x0F, b" [UP/DOWN] Wybor  [F7] Katalog  [ESC] Wyjdz ");
+    }
+
+    fn on_key(&mut self, key: KeyEvent) -> Action {
+        if !key.pressed { return Action::None; }
+
+        if let Some(name) = self.confirm_delete {
+            if key.repeat { return Action::None; }
+            self.confirm_delete = None;
+            return self.answer_delete(name, key);
+        }
+
+        if self.message_open {
+            if key.repeat { return Action::None; }
+            self.message_open = false;
+            self.draw();
+            self.refresh_list();
+            return Action::None;
+        }
+
+        match key.scancode {
+            KEY_UP => { // Strzałki powtarzają się przy przytrzymaniu
+                if self.selection > 0 { self.selection -= 1; }
+                self.refresh_list();
+            }
+            KEY_DOWN => {
+                if self.selection + 1 < self.file_count { self.selection += 1; }
+                self.refresh_list();
+            }
+            _ if key.repeat => {}
+            KEY_F4 if keyboard::shift_down() => { // Shift+F4 - Utwórz plik
+                return Self::ask_name(b" UTWORZ PLIK ", InputContext::CreateFile { dir: self.dir });
+            }
+            KEY_F7 => { // F7 - Utwórz katalog
+                return Self::ask_name(b" UTWORZ KATALOG ", InputContext::CreateDir { dir: self.dir });
+            }
+            KEY_ENTER => { // ENTER - Otwórz zaznaczony (gdy istnieją pliki)
+                match self.entry_at(self.selection) {
+                    Some(item) if item.entry.is_dir() => return self.change_dir(&item),
+                    Some(item) => {
+                        return match editor::open_file(self.dir, &item.name) {
+                            Ok(()) => Action::Push(AppId::Editor),
+                            Err(error) => menu::show_error(b" OTWORZ PLIK ", error),
+                        };
+                    }
+                    None => {
+                        vga_print!(2, 22, 0x0E, b"Brak plikow - uzyj Shift+F4 aby utworzyc");
+                    }
+                }
+            }
+            KEY_F9 => self.check_volume(),
+            KEY_F6 | KEY_F8 | KEY_F5 => {
+                let Some(item) = self.entry_at(self.selection) else { return Action::None };
+                let (entry, name) = (item.entry, item.name);
+                if entry.short_name().is_dot() { return Action::None; } // `..` nie jest zwykłym wpisem
+                let dir = self.dir;
+                match key.scancode {
+                    KEY_F6 => { // F6 - Zmiana nazwy
+                        return Self::ask_name(b" ZMIEN NAZWE ", InputContext::RenameFile { dir, from: name });
+                    }
+                    KEY_F8 => { // F8 - Usuń (po potwierdzeniu; katalog tylko pusty)
+                        let prompt: &[u8] = if entry.is_dir() {
+                            b"Usunac zaznaczony katalog? [T/N]"
+                        } else {
+                            b"Usunac zaznaczony plik? [T/N]"
+                        };
+                        Self::dialog(b" USUN ", prompt);
+                        let (text, len) = name.fitted_text(56);
+                        vga_print!(12, 13, 0x1E, &text[..len]);
+                        self.confirm_delete = Some(name);
+                    }
+                    _ if keyboard::shift_down() && !entry.is_dir() => { // Shift+F5 - Kopia
+                        return Self::ask_name(b" KOPIUJ PLIK ", InputContext::CopyFile { dir, from: name });
+                    }
+                    _ => {}
+                }
+            }
+            KEY_ESC => return Action::Pop,
+            _ => {}
+        }
+        Action::None
+    }
+}
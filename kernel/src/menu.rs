@@ -0,0 +1,362 @@
+// =============================================================================
+// MENU - ekrany tekstowe: Main, Math, Unicode oraz pasek INPUT
+// =============================================================================
+
+use crate::app::{Action, App, AppId};
+use crate::keyboard::{self, Key, KeyEvent};
+use crate::fs::fat16::{self, Fat16Error, FileName};
+use crate::state::{self, InputContext, Message};
+
+const KEY_ESC: u8 = 0x01;
+const KEY_BACKSPACE: u8 = 0x0E;
+const KEY_ENTER: u8 = 0x1C;
+const KEY_HOME: u8 = 0x47;
+
+// --- MAIN MENU ---
+
+pub struct MainMenu {
+    row: u64, // Wiersz komunikatów "Rusted Mx"
+}
+
+impl MainMenu {
+    pub const fn new() -> Self {
+        MainMenu { row: 0 }
+    }
+}
+
+impl Default for MainMenu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl App for MainMenu {
+    fn draw(&self) {
+        vga_clear!(0x00); // Czarny ekran
+
+        // vga_print_ext!(0, 0, b"Rusted\0", [0x0F, 0x0A, 0x0E, 0x0C, 0x0B, 0x05]);
+
+        vga_write!(0, 0, b'R', 0x0F);
+        vga_write!(1, 0, b'u', 0x0A);
+        vga_write!(2, 0, b's', 0x0E);
+        vga_write!(3, 0, b't', 0x0C);
+        vga_write!(4, 0, b'e', 0x0B);
+        vga_write!(5, 0, b'd', 0x05);
+
+        // Click Key 1 - Mode1
+        vga_print!(0, 2, 0x0F, b"Click");
+        vga_print!(6, 2, 0x0C, b"Key 1");
+        vga_write!(12, 2, b'-', 0x0F);
+        vga_print!(14, 2, 0x0A, b"Mode1");
+
+        // Click Key 2 - Mode2
+        vga_print!(0, 3, 0x0F, b"Click");
+        vga_print!(6, 3, 0x0A, b"Key 2");
+        vga_write!(12, 3, b'-', 0x0F);
+        vga_print!(14, 3, 0x0B, b"Mode2");
+
+        // Click Key 3 - Mode3
+        vga_print!(0, 4, 0x0F, b"Click");
+        vga_print!(6, 4, 0x0E, b"Key 3");
+        vga_write!(12, 4, b'-', 0x0F);
+        vga_print!(14, 4, 0x0C, b"Mode3");
+
+        // Click Key 4 - Line
+        vga_print!(0, 5, 0x0F, b"Click");
+        vga_print!(6, 5, 0x09, b"Key 4");
+        vga_write!(12, 5, b'-', 0x0F);
+        vga_print!(14, 5, 0x0D, b"Line");
+
+
+        // Click Key 9 - Secrets
+        vga_print!(0, 6, 0x0F, b"Click");
+        vga_print!(6, 6, 0x01, b"Key 9");
+        vga_write!(12, 6, b'-', 0x0F);
+        vga_print!(14, 6, 0x07, b"Secrets");
+
+        // Click Key M - Math
+        vga_print!(0, 7, 0x0F, b"Click");
+        vga_print!(6, 7, 0x0D, b"Key M");
+        vga_write!(12, 7, b'-', 0x0F);
+        vga_print!(14, 7, 0x09, b"Math");
+
+        // Click Key 0 - Start
+        vga_print!(0, 8, 0x0F, b"Click");
+        vga_print!(6, 8, 0x0D, b"Key 0");
+        vga_write!(12, 8, b'-', 0x0F);
+        vga_print!(14, 8, 0x0E, b"Start");
+
+        // Click ESC - Clear
+        vga_print!(0, 10, 0x0F, b"Click");
+        vga_print!(6, 10, 0x05, b"ESC");
+        vga_write!(10, 10, b'-', 0x0F);
+        vga_print!(12, 10, 0x04, b"CLEAR");
+
+        // Click F5 - Reboot
+        vga_print!(0, 11, 0x0F, b"Click");
+        vga_print!(6, 11, 0x02, b"F5");
+        vga_write!(10, 11, b'-', 0x0F);
+        vga_print!(12, 11, 0x06, b"REBOOT");
+    }
+
+    fn on_key(&mut self, key: KeyEvent) -> Action {
+        if !key.pressed || key.repeat { return Action::None; }
+
+        match key.scancode {
+            0x02 => { vga_print!(0, self.row, 0x0A, b"Rusted M1"); }
+            0x03 => { vga_print!(0, self.row, 0x0E, b"Rusted M2"); }
+            0x04 => { vga_print!(0, self.row, 0x0C, b"Rusted M3"); }
+            0x05 => { vga_draw_rect!(0, 12, 80, 1, 0x02); } // Key 4 - Line (rysuj poziomą linię)
+            0x0A => { /* Logika Secrets... */ }
+            0x32 => return Action::Push(AppId::Math), // M - Math
+            0x17 => { // I - Input
+                state::with(|s| s.input_context = InputContext::Echo);
+                return Action::Push(AppId::Input);
+            }
+            KEY_HOME => return Action::Push(AppId::MiniCommander),
+            // F8 - Unicode (kody z zestawów 1/2/3 zgłaszane przez różne emulatory)
+            0x42 | 0x07 | 0x64 | 0x09 => return Action::Push(AppId::Unicode),
+            KEY_ESC => { vga_clear!(0x00); }
+            _ => {}
+        }
+        Action::None
+    }
+}
+
+// --- MATH MENU ---
+
+pub struct MathMenu;
+
+impl App for MathMenu {
+    fn draw(&self) {
+        vga_clear!(0x00);
+        vga_print!(0, 0, 0x0F, b"MATH MENU: 1-Add, 2-Sub, 3-Mul, 4-Div, 9-Rand, 0-Back");
+    }
+
+    fn on_key(&mut self, key: KeyEvent) -> Action {
+        if !key.pressed || key.repeat { return Action::None; }
+
+        match key.scancode {
+            0x0B => return Action::Pop, // Klawisz 0 - Powrót
+            0x02 => { // Klawisz 1 w Math
+                vga_print!(0, 10, 0x0A, b"Addition selected!");
+                let add_result = 2 + 2;
+                vga_print!(0, 11, 0x0F, b"2 + 2 = ");
+                let res_char = (add_result as u8) + b'0';
+                vga_write!(8, 11, res_char, 0x0F);
+            }
+            0x03 => { // Klawisz 2 w Math
+                vga_print!(0, 10, 0x0E, b"Subtraction selected!");
+                let sub_result = 5 - 3;
+                vga_print!(0, 11, 0x0F, b"5 - 3 = ");
+                let res_char = (sub_result as u8) + b'0';
+                vga_write!(8, 11, res_char, 0x0F);
+            }
+            0x04 => { // Klawisz 3 w Math
+                vga_print!(0, 10, 0x0C, b"Multiplication selected!");
+                let mul_result = 3 * 4;
+                vga_print!(0, 11, 0x0F, b"3 * 4 = ");
+                let res_char = (mul_result as u8) + b'0';
+                vga_write!(8, 11, res_char, 0x0F);
+            }
+            0x05 => { // Klawisz 4 w Math
+                vga_print!(0, 10, 0x09, b"Division selected!");
+                let div_result = 8 / 2;
+                vga_print!(0, 11, 0x0F, b"8 / 2 = ");
+                let res_char = (div_result as u8) + b'0';
+                vga_write!(8, 11, res_char, 0x0F);
+            }
+            0x0A => { // Klawisz 9 w Math
+                let random_number = (get_random!() % 100) as u8; // Zakres 0-99
+                let tens = random_number / 10;
+                let ones = random_number % 10;
+
+                vga_print!(0, 10, 0x07, b"Random Number: ");
+                let mut col = 15;
+                if tens > 0 {
+                    vga_write!(col, 10, tens + b'0', 0x07);
+                    col += 1;
+                }
+                vga_write!(col, 10, ones + b'0', 0x07);
+            }
+            // Tutaj możesz dodać resztę klawiszy dla Math
+            _ => {}
+        }
+        Action::None
+    }
+}
+
+// --- UNICODE MENU ---
+
+pub struct UnicodeMenu;
+
+impl App for UnicodeMenu {
+    fn on_enter(&mut self) {
+        set_encoding!(UTF8);
+        self.draw();
+    }
+
+    fn on_exit(&mut self) {
+        set_encoding!(ASCII);
+    }
+
+    fn draw(&self) {
+        crate::vga::unicode_menu();
+    }
+
+    fn on_key(&mut self, key: KeyEvent) -> Action {
+        if !key.pressed || key.repeat { return Action::None; }
+
+        match key.scancode {
+            0x0B => return Action::Pop, // Klawisz 0 - Powrót do Main Menu
+            0x02 => { // Klawisz 1 - 720p Demo
+                vga_clear!(0x00);
+                vga_print!(0, 5, 0x0C, b"Tryb 720p wymaga VM86!");
+                vga_print!(0, 6, 0x0C, b"W kernelu nie mozna uzywac INT 0x10");
+                vga_print!(0, 8, 0x0E, b"Nacisnij 0 aby wrocic");
+            }
+            _ => {}
+        }
+        Action::None
+    }
+}
+
+// --- PASEK INPUT (wiersz 24) ---
+// Nakładka na ekran, który go otworzył. Co stanie się z tekstem po ENTER
+// decyduje `KernelState::input_context`.
+
+pub struct InputPrompt;
+
+impl InputPrompt {
+    fn submit(&mut self) -> Action {
+        let (len, context, input) = state::with(|s| (s.input_len, s.input_context, s.input));
+        if len == 0 { return Action::None; }
+
+        if context.is_file_dialog() {
+            // Nazwa długa albo 8.3 ("MOJE NOTATKI.TXT"); błędną zgłasza okno
+            // jak każdy błąd FAT16
+            let name = FileName::parse(&input[..len]);
+            let (title, result, message): (&'static [u8], _, &[u8]) = match context {
+                InputContext::RenameFile { dir, from } => {
+                    (b" ZMIEN NAZWE ", name.and_then(|to| fat16::fat16_rename(dir, &from, &to)), b"Nazwa zmieniona")
+                }
+                InputContext::CopyFile { dir, from } => {
+                    (b" KOPIUJ PLIK ", name.and_then(|to| fat16::fat16_copy(dir, &from, &to)), b"Plik skopiowany")
+                }
+                InputContext::CreateDir { dir } => {
+                    (b" UTWORZ KATALOG ", name.and_then(|name| fat16::fat16_mkdir(dir, &name)), b"Katalog utworzony")
+                }
+                InputContext::CreateFile { dir } => {
+                    (b" UTWORZ PLIK ", name.and_then(|name| fat16::fat16_create_file(dir, &name)), b"Plik utworzony")
+                }
+                InputContext::Echo => return Action::None, // Wykluczone przez is_file_dialog
+            };
+            state::with(|s| s.leave_input());
+            if let Err(error) = result {
+                // Okno błędu zastępuje pasek INPUT; po nim wracamy do listy
+                state::with(|s| s.message = Message { title, text: error.message() });
+                return Action::Replace(AppId::Message);
+            }
+            vga_print!(0, 22, 0x0A, message);
+            sleep_time!(250);
+            return Action::Pop; // Mini-Commander odświeży listę w on_enter
+        }
+
+        // Domyślna prezentacja ostatniego inputu (InputContext::Echo)
+        vga_print!(0, 22, 0x0E, b"OSTATNI INPUT: \0");
+        vga_print!(15, 22, 0x0F, &input[..len]);
+        state::with(|s| s.leave_input());
+        sleep_time!(500);
+        vga_clear_animated!(0, 24, 0x00, 300, 100);
+        Action::Dismiss
+    }
+}
+
+impl App for InputPrompt {
+    fn draw(&self) {
+        let (context, input, len) = state::with(|s| (s.input_context, s.input, s.input_len));
+        vga_input_setup!();
+        if context.is_file_dialog() {
+            vga_print!(0, 24, 0x70, b" NAME  > ");
+        }
+        vga_print!(9, 24, 0x70, &input[..len]);
+    }
+
+    fn on_key(&mut self, key: KeyEvent) -> Action {
+        if !key.pressed || key.repeat { return Action::None; }
+
+        match key.scancode {
+            KEY_ENTER => self.submit(),
+            KEY_ESC => {
+                let context = state::with(|s| {
+                    let context = s.input_context;
+                    s.leave_input();
+                    context
+                });
+                if context.is_file_dialog() { return Action::Pop; }
+                vga_clear_animated!(0, 24, 0x00, 300, 100);
+                Action::Dismiss
+            }
+            KEY_BACKSPACE => {
+                let len = state::with(|s| {
+                    if s.input_len > 0 { s.input_len -= 1; }
+                    s.input_len
+                });
+                vga_write!(9 + len as u64, 24, b' ', 0x70);
+                Action::None
+            }
+            KEY_HOME if keyboard::decode(&key) == Key::Home => { // HOME -> Mini-Commander (7 z Num Lock to cyfra)
+                let context = state::with(|s| {
+                    let context = s.input_context;
+                    s.leave_input();
+                    context
+                });
+                if context.is_file_dialog() { return Action::Pop; }
+                Action::Replace(AppId::MiniCommander)
+            }
+            _ => {
+                // Wspólny dekoder: Shift, Caps Lock, cyfry i symbole, Alt + litera -> polski glif
+                if let Key::Char(c) = keyboard::decode(&key) {
+                    let len = state::with(|s| s.input_len);
+                    if state::with(|s| s.push_input(c)) {
+                        vga_write!(9 + len as u64, 24, c, 0x70);
+                    }
+                }
+                Action::None
+            }
+        }
+    }
+}
+
+// --- OKNO KOMUNIKATU ---
+// Nakładka z tytułem i jednym wierszem tekstu (np. błąd systemu plików).
+// Dowolny klawisz zamyka okno i przerysowuje ekran pod spodem.
+
+pub struct MessageBox;
+
+// Pokazuje komunikat w oknie nad bieżącym ekranem
+pub fn show_message(title: &'static [u8], text: &'static [u8]) -> Action {
+    state::with(|s| s.message = Message { title, text });
+    Action::Push(AppId::Message)
+}
+
+// Pokazuje błąd FAT16 w oknie nad bieżącym ekranem
+pub fn show_error(title: &'static [u8], error: Fat16Error) -> Action {
+    show_message(title, error.message())
+}
+
+impl App for MessageBox {
+    fn draw(&self) {
+        let message = state::with(|s| s.message);
+        vga_draw_rect!(9, 7, 62, 10, 0x07); // szara ramka
+        vga_draw_rect!(10, 8, 60, 8, 0x04); // czerwone wnętrze
+        vga_print!(12, 9, 0x4F, message.title);
+        vga_print!(12, 11, 0x4F, message.text);
+        vga_print!(12, 14, 0x4E, b"[dowolny klawisz]");
+    }
+
+    fn on_key(&mut self, key: KeyEvent) -> Action {
+        if !key.pressed || key.repeat { return Action::None; }
+        Action::Pop
+    }
+}
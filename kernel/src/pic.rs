@@ -0,0 +1,75 @@
+// =============================================================================
+// 8259 PIC - remapowanie przerwań sprzętowych
+// =============================================================================
+// Po starcie BIOS mapuje IRQ0-7 na wektory 0x08-0x0F, które w trybie
+// chronionym/long mode kolidują z wyjątkami CPU. Przenosimy oba układy na
+// 0x20-0x2F i domyślnie maskujemy wszystko poza kaskadą (IRQ2).
+// =============================================================================
+
+use crate::io::{inb, io_wait, outb};
+
+pub const PIC1_OFFSET: u8 = 0x20;
+pub const PIC2_OFFSET: u8 = 0x28;
+
+const PIC1_CMD: u16 = 0x20;
+const PIC1_DATA: u16 = 0x21;
+const PIC2_CMD: u16 = 0xA0;
+const PIC2_DATA: u16 = 0xA1;
+
+const ICW1_INIT: u8 = 0x11; // Inicjalizacja + oczekiwanie na ICW4
+const ICW4_8086: u8 = 0x01;
+const CMD_EOI: u8 = 0x20;
+const CMD_READ_ISR: u8 = 0x0B;
+
+pub fn init() {
+    unsafe {
+        outb(PIC1_CMD, ICW1_INIT); io_wait();
+        outb(PIC2_CMD, ICW1_INIT); io_wait();
+        outb(PIC1_DATA, PIC1_OFFSET); io_wait(); // ICW2: wektor bazowy
+        outb(PIC2_DATA, PIC2_OFFSET); io_wait();
+        outb(PIC1_DATA, 0x04); io_wait();        // ICW3: slave na IRQ2
+        outb(PIC2_DATA, 0x02); io_wait();        // ICW3: tożsamość slave
+        outb(PIC1_DATA, ICW4_8086); io_wait();
+        outb(PIC2_DATA, ICW4_8086); io_wait();
+
+        // Wszystko zamaskowane poza kaskadą - sterowniki odmaskowują swoje linie
+        outb(PIC1_DATA, 0xFB);
+        outb(PIC2_DATA, 0xFF);
+    }
+}
+
+pub fn unmask(irq: u8) {
+    let (port, bit) = if irq < 8 { (PIC1_DATA, irq) } else { (PIC2_DATA, irq - 8) };
+    unsafe {
+        let mask = inb(port) & !(1 << bit);
+        outb(port, mask);
+    }
+}
+
+fn read_isr(cmd_port: u16) -> u8 {
+    unsafe {
+        outb(cmd_port, CMD_READ_ISR);
+        inb(cmd_port)
+    }
+}
+
+// IRQ7/IRQ15 mogą być fałszywe (spurious) - wtedy bit w ISR nie jest ustawiony
+// i nie wolno wysłać EOI do układu, który przerwania nie zgłosił.
+pub fn is_spurious(irq: u8) -> bool {
+    match irq {
+        7 => read_isr(PIC1_CMD) & 0x80 == 0,
+        15 if read_isr(PIC2_CMD) & 0x80 == 0 => {
+            // Master i tak zgłosił IRQ2 (kaskada), więc potwierdzamy tylko jemu
+            unsafe { outb(PIC1_CMD, CMD_EOI); }
+            true
+        }
+        _ => false,
+    }
+}
+
+pub fn end_of_interrupt(irq: u8) {
+    unsafe {
+        if irq >= 8 { outb(PIC2_CMD, CMD_EOI); }
+        outb(PIC1_CMD, CMD_EOI);
+    }
+}
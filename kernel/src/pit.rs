@@ -0,0 +1,28 @@
+// =============================================================================
+// 8253/8254 PIT - programowalny licznik, kanał 0 na IRQ0
+// =============================================================================
+// Kanał 0 taktowany jest zegarem 1.193182 MHz niezależnie od hosta (QEMU TCG,
+// KVM, sprzęt), więc w odróżnieniu od pętli `dec/jnz` daje stały czas.
+// Licznikiem ticków i API czasu zajmuje się `time.rs`.
+// =============================================================================
+
+use crate::io::outb;
+
+pub const BASE_FREQUENCY: u32 = 1_193_182; // Hz
+
+const CHANNEL0_DATA: u16 = 0x40;
+const COMMAND: u16 = 0x43;
+
+// Kanał 0, dostęp lo/hi, tryb 2 (rate generator), licznik binarny
+const CMD_CHANNEL0_RATE: u8 = 0x34;
+
+// Programuje kanał 0 na zadaną częstotliwość i zwraca użyty dzielnik
+pub fn init(hz: u32) -> u16 {
+    let divisor = (BASE_FREQUENCY / hz).clamp(1, 0xFFFF) as u16;
+    unsafe {
+        outb(COMMAND, CMD_CHANNEL0_RATE);
+        outb(CHANNEL0_DATA, divisor as u8);
+        outb(CHANNEL0_DATA, (divisor >> 8) as u8);
+    }
+    divisor
+}
@@ -0,0 +1,107 @@
+// =============================================================================
+// CMOS RTC - zegar czasu rzeczywistego (porty 0x70/0x71)
+// =============================================================================
+// Rejestry czytamy, gdy nie trwa aktualizacja (bit UIP w rejestrze A), i
+// powtarzamy odczyt, aż dwa kolejne dadzą to samo - aktualizacja mogła
+// zacząć się w trakcie. Rejestr B mówi, czy wartości są w BCD czy binarnie
+// i czy godzina jest 12- czy 24-godzinna.
+// Rejestr stulecia nie jest standardowy (adres podaje dopiero ACPI), więc
+// rok dwucyfrowy rozwijamy w zakresie FAT: 80-99 -> 19xx, 00-79 -> 20xx.
+// =============================================================================
+
+use crate::io::{inb, outb};
+use crate::time::date::DateTime;
+
+const CMOS_ADDRESS: u16 = 0x70;
+const CMOS_DATA: u16 = 0x71;
+
+const REG_SECONDS: u8 = 0x00;
+const REG_MINUTES: u8 = 0x02;
+const REG_HOURS: u8 = 0x04;
+const REG_DAY: u8 = 0x07;
+const REG_MONTH: u8 = 0x08;
+const REG_YEAR: u8 = 0x09;
+const REG_STATUS_A: u8 = 0x0A;
+const REG_STATUS_B: u8 = 0x0B;
+
+const STATUS_A_UPDATE: u8 = 0x80; // Trwa aktualizacja (do ~2 ms co sekundę)
+const STATUS_B_24H: u8 = 0x02;
+const STATUS_B_BINARY: u8 = 0x04;
+const HOUR_PM: u8 = 0x80;         // W trybie 12h najstarszy bit godziny
+
+const UPDATE_WAIT_READS: usize = 10_000; // ~10 ms odczytów portu
+const MAX_READS: usize = 5;
+
+fn read_register(register: u8) -> u8 {
+    // Bit 7 adresu zostawiamy zgaszony - nie blokujemy NMI
+    unsafe {
+        outb(CMOS_ADDRESS, register);
+        inb(CMOS_DATA)
+    }
+}
+
+// Czeka na koniec aktualizacji; false, gdy UIP nie gaśnie (brak RTC)
+fn wait_for_update() -> bool {
+    (0..UPDATE_WAIT_READS).any(|_| read_register(REG_STATUS_A) & STATUS_A_UPDATE == 0)
+}
+
+// Surowe rejestry zegara - porównywane między kolejnymi odczytami
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RawTime {
+    second: u8,
+    minute: u8,
+    hour: u8,
+    day: u8,
+    month: u8,
+    year: u8,
+}
+
+fn read_raw() -> RawTime {
+    RawTime {
+        second: read_register(REG_SECONDS),
+        minute: read_register(REG_MINUTES),
+        hour: read_register(REG_HOURS),
+        day: read_register(REG_DAY),
+        month: read_register(REG_MONTH),
+        year: read_register(REG_YEAR),
+    }
+}
+
+fn from_bcd(value: u8) -> u8 {
+    (value >> 4) * 10 + (value & 0x0F)
+}
+
+fn decode(raw: RawTime, status_b: u8) -> DateTime {
+    let convert = |value: u8| if status_b & STATUS_B_BINARY != 0 { value } else { from_bcd(value) };
+    let pm = raw.hour & HOUR_PM != 0;
+    let mut hour = convert(raw.hour & !HOUR_PM);
+    if status_b & STATUS_B_24H == 0 {
+        // 12h: 12 AM = 0, 12 PM = 12
+        hour = hour % 12 + if pm { 12 } else { 0 };
+    }
+    let year = convert(raw.year) as u16;
+    DateTime {
+        year: if year < 80 { 2000 + year } else { 1900 + year },
+        month: convert(raw.month),
+        day: convert(raw.day),
+        hour,
+        minute: convert(raw.minute),
+        second: convert(raw.second),
+    }
+}
+
+// Bieżąca data i czas z CMOS; None, gdy zegar nie odpowiada albo podaje
+// niemożliwą datę
+pub fn now() -> Option<DateTime> {
+    let mut last = None;
+    for _ in 0..MAX_READS {
+        if !wait_for_update() { return None; }
+        let raw = read_raw();
+        if last == Some(raw) {
+            let time = decode(raw, read_register(REG_STATUS_B));
+            return time.is_valid().then_some(time);
+        }
+        last = Some(raw);
+    }
+    None
+}
@@ -0,0 +1,101 @@
+// =============================================================================
+// PORT SZEREGOWY COM1 (16550 UART)
+// =============================================================================
+// QEMU uruchamiany z `-serial stdio` wypisuje wszystko co trafi na COM1
+// bezpośrednio w terminalu - przydatne gdy ekran VGA jest już nieczytelny.
+// Formatowany wydruk: makra `serial_print!` / `serial_println!`.
+// =============================================================================
+
+use core::fmt;
+use core::sync::atomic::{AtomicBool, Ordering};
+use crate::io::{inb, outb};
+
+pub const COM1: u16 = 0x3F8;
+
+// Rejestry względem adresu bazowego
+const REG_DATA: u16 = 0;        // DLAB=1: młodszy bajt dzielnika
+const REG_INT_ENABLE: u16 = 1;  // DLAB=1: starszy bajt dzielnika
+const REG_FIFO_CTRL: u16 = 2;
+const REG_LINE_CTRL: u16 = 3;
+const REG_MODEM_CTRL: u16 = 4;
+const REG_LINE_STATUS: u16 = 5;
+
+const LSR_TRANSMIT_EMPTY: u8 = 0x20;
+
+// Ustawiane po udanym teście pętli zwrotnej - bez UART-a nie czekamy na THRE
+static PRESENT: AtomicBool = AtomicBool::new(false);
+
+pub struct SerialPort {
+    base: u16,
+}
+
+impl SerialPort {
+    pub const fn new(base: u16) -> Self {
+        SerialPort { base }
+    }
+
+    // Zwraca false, gdy układ nie przeszedł testu pętli zwrotnej
+    pub fn init(&self) -> bool {
+        let base = self.base;
+        unsafe {
+            outb(base + REG_INT_ENABLE, 0x00); // Wyłącz przerwania UART
+            outb(base + REG_LINE_CTRL, 0x80);  // DLAB=1 - dostęp do dzielnika
+            outb(base + REG_DATA, 0x01);       // Dzielnik 1 = 115200 bodów
+            outb(base + REG_INT_ENABLE, 0x00);
+            outb(base + REG_LINE_CTRL, 0x03);  // 8 bitów, brak parzystości, 1 bit stopu
+            outb(base + REG_FIFO_CTRL, 0xC7);  // FIFO włączone, wyczyszczone, próg 14 bajtów
+
+            // Test: tryb loopback, wysyłamy bajt i oczekujemy go z powrotem
+            outb(base + REG_MODEM_CTRL, 0x1E);
+            outb(base + REG_DATA, 0xAE);
+            if inb(base + REG_DATA) != 0xAE {
+                return false;
+            }
+
+            outb(base + REG_MODEM_CTRL, 0x0F); // Normalna praca: DTR + RTS + OUT1/OUT2
+        }
+        true
+    }
+
+    fn transmit_empty(&self) -> bool {
+        unsafe { inb(self.base + REG_LINE_STATUS) & LSR_TRANSMIT_EMPTY != 0 }
+    }
+
+    pub fn write_byte(&self, byte: u8) {
+        if byte == b'\n' { self.write_byte(b'\r'); }
+        while !self.transmit_empty() {}
+        unsafe { outb(self.base + REG_DATA, byte); }
+    }
+}
+
+impl fmt::Write for SerialPort {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for &b in s.as_bytes() {
+            self.write_byte(b);
+        }
+        Ok(())
+    }
+}
+
+pub fn init() {
+    let ok = SerialPort::new(COM1).init();
+    PRESENT.store(ok, Ordering::Relaxed);
+}
+
+pub fn write_byte(byte: u8) {
+    if !PRESENT.load(Ordering::Relaxed) { return; }
+    SerialPort::new(COM1).write_byte(byte);
+}
+
+pub fn write_bytes(bytes: &[u8]) {
+    for &b in bytes {
+        write_byte(b);
+    }
+}
+
+// Wywoływane przez makra serial_print!/serial_println!
+pub fn _print(args: fmt::Arguments) {
+    use core::fmt::Write;
+    if !PRESENT.load(Ordering::Relaxed) { return; }
+    let _ = SerialPort::new(COM1).write_fmt(args);
+}
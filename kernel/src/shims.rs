@@ -16,6 +16,19 @@ pub extern "C" fn memcpy(d: *mut u8, s: *const u8, n: usize) -> *mut u8 {
     d
 }
 
+/// # Safety
+/// `d` i `s` muszą wskazywać `n` bajtów; obszary mogą na siebie nachodzić
+/// (`copy_within`), dlatego przy `d > s` kopiujemy od końca.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memmove(d: *mut u8, s: *const u8, n: usize) -> *mut u8 {
+    if (d as usize) > (s as usize) {
+        for i in (0..n).rev() { unsafe { *d.add(i) = *s.add(i); } }
+    } else {
+        for i in 0..n { unsafe { *d.add(i) = *s.add(i); } }
+    }
+    d
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn memset(s: *mut u8, c: i32, n: usize) -> *mut u8 {
     for i in 0..n { unsafe { *s.add(i) = c as u8; } }
@@ -0,0 +1,104 @@
+// =============================================================================
+// STAN JĄDRA - zamiast luźnych bajtów pod 0x500-0x512 i bufora pod 0x600
+// =============================================================================
+// Dawna mapa pamięci (dla porównania ze starszymi wpisami CHANGELOG):
+// 0x500 menu, 0x501 ostatni scancode, 0x503 blokada animacji, 0x505 długość
+// inputu, 0x506 tryb input, 0x507 ALT, 0x508 kodowanie, 0x509 flaga 720p,
+// 0x510 wybór w MC, 0x511 kontekst inputu, 0x512 liczba plików, 0x600 bufor.
+//
+// Stan leży teraz w .bss jądra i jest dostępny wyłącznie przez `state::with`,
+// który pilnuje, żeby nie powstały dwie równoczesne referencje `&mut`.
+// Bieżący ekran i wybór w MC trzyma stos aplikacji (app.rs), nie ten stan.
+// =============================================================================
+
+use crate::fs::fat16::{Dir, FileName};
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+pub const INPUT_MAX: usize = 60; // Tyle znaków mieści pasek INPUT (od kolumny 9)
+
+// Co zrobić z tekstem zatwierdzonym ENTER-em w trybie input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputContext {
+    Echo,                                     // Pokaż "OSTATNI INPUT"
+    CreateFile { dir: Dir },                  // Nazwa nowego pliku (Shift+F4 w Mini-Commanderze)
+    CreateDir { dir: Dir },                   // Nazwa nowego katalogu (F7)
+    RenameFile { dir: Dir, from: FileName },  // Nowa nazwa pliku lub katalogu (F6)
+    CopyFile { dir: Dir, from: FileName },    // Nazwa kopii (Shift+F5)
+}
+
+impl InputContext {
+    // Konteksty Mini-Commandera - po zakończeniu wracamy do listy plików
+    pub fn is_file_dialog(&self) -> bool {
+        !matches!(self, InputContext::Echo)
+    }
+}
+
+// Treść okna komunikatu (AppId::Message)
+#[derive(Debug, Clone, Copy)]
+pub struct Message {
+    pub title: &'static [u8],
+    pub text: &'static [u8],
+}
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Ascii = 0,
+    Utf8 = 1,
+}
+
+pub struct KernelState {
+    #[allow(dead_code)]
+    pub anim_lock: bool, // Blokada animacji (key 9) - zarezerwowane
+    pub input_context: InputContext,
+    pub input_len: usize,
+    pub input: [u8; INPUT_MAX],
+    pub message: Message,
+    pub encoding: Encoding,
+    #[allow(dead_code)]
+    pub hd_mode: bool,   // Flaga 720p - zarezerwowane pod tryb VBE
+}
+
+impl KernelState {
+    const fn new() -> Self {
+        KernelState {
+            anim_lock: false,
+            input_context: InputContext::Echo,
+            input_len: 0,
+            input: [0; INPUT_MAX],
+            message: Message { title: b"", text: b"" },
+            encoding: Encoding::Ascii,
+            hd_mode: false,
+        }
+    }
+
+    pub fn push_input(&mut self, c: u8) -> bool {
+        if self.input_len >= INPUT_MAX { return false; }
+        self.input[self.input_len] = c;
+        self.input_len += 1;
+        true
+    }
+
+    pub fn leave_input(&mut self) {
+        self.input_len = 0;
+        self.input_context = InputContext::Echo;
+    }
+}
+
+struct StateCell(UnsafeCell<KernelState>);
+unsafe impl Sync for StateCell {}
+
+static STATE: StateCell = StateCell(UnsafeCell::new(KernelState::new()));
+static BORROWED: AtomicBool = AtomicBool::new(false);
+
+// Jedyny punkt dostępu do stanu. Zagnieżdżone wywołanie to błąd logiki UI
+// (np. rysowanie listy plików wewnątrz domknięcia) - kończy się panic.
+pub fn with<R>(f: impl FnOnce(&mut KernelState) -> R) -> R {
+    if BORROWED.swap(true, Ordering::Acquire) {
+        panic!("state::with called re-entrantly");
+    }
+    let result = f(unsafe { &mut *STATE.0.get() });
+    BORROWED.store(false, Ordering::Release);
+    result
+}
@@ -0,0 +1,93 @@
+// =============================================================================
+// CZAS - monotoniczny licznik ticków PIT (IRQ0)
+// =============================================================================
+// Tick co ~1 ms. `uptime()` przelicza ticki dokładnym dzielnikiem PIT, więc
+// nie dryfuje mimo że 1193182 / 1000 nie jest liczbą całkowitą.
+// Użycie:
+//   time::sleep_ms(250);
+//   let deadline = Deadline::after_ms(500);
+//   while !deadline.expired() { ... }
+// =============================================================================
+
+pub mod date;
+
+use core::sync::atomic::{AtomicU16, AtomicU64, Ordering};
+use core::time::Duration;
+use crate::{interrupts, pic, pit};
+
+pub const TICK_HZ: u32 = 1000;
+
+static TICKS: AtomicU64 = AtomicU64::new(0); // Zapisuje tylko IRQ0
+static DIVISOR: AtomicU16 = AtomicU16::new(0); // 0 = zegar jeszcze nie działa
+
+pub fn init() {
+    let divisor = pit::init(TICK_HZ);
+    DIVISOR.store(divisor, Ordering::Relaxed);
+    pic::unmask(interrupts::IRQ_TIMER);
+}
+
+// Wywoływane z interrupt_dispatch
+pub fn handle_irq() {
+    TICKS.fetch_add(1, Ordering::Relaxed);
+}
+
+pub fn ticks() -> u64 {
+    TICKS.load(Ordering::Relaxed)
+}
+
+fn ticks_to_nanos(ticks: u64) -> u64 {
+    let divisor = DIVISOR.load(Ordering::Relaxed) as u128;
+    (ticks as u128 * divisor * 1_000_000_000 / pit::BASE_FREQUENCY as u128) as u64
+}
+
+fn ms_to_ticks(ms: u64) -> u64 {
+    let divisor = DIVISOR.load(Ordering::Relaxed).max(1) as u64;
+    // Zaokrąglamy w górę - sleep nigdy nie może być krótszy niż żądany
+    (ms * pit::BASE_FREQUENCY as u64).div_ceil(divisor * 1000)
+}
+
+// Czas od uruchomienia zegara (time::init)
+pub fn uptime() -> Duration {
+    Duration::from_nanos(ticks_to_nanos(ticks()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deadline {
+    tick: u64,
+}
+
+impl Deadline {
+    pub fn after_ms(ms: u64) -> Self {
+        Deadline { tick: ticks() + ms_to_ticks(ms) }
+    }
+
+    // Termin przesunięty o `ms` względem tego
+    pub fn plus_ms(&self, ms: u64) -> Self {
+        Deadline { tick: self.tick + ms_to_ticks(ms) }
+    }
+
+    pub fn expired(&self) -> bool {
+        ticks() >= self.tick
+    }
+
+    #[allow(dead_code)]
+    pub fn remaining(&self) -> Duration {
+        Duration::from_nanos(ticks_to_nanos(self.tick.saturating_sub(ticks())))
+    }
+
+    // Usypia (hlt) do upływu terminu
+    pub fn wait(&self) {
+        if DIVISOR.load(Ordering::Relaxed) == 0 || !interrupts::are_enabled() {
+            // Bez IRQ0 licznik stoi - czekanie zawiesiłoby jądro
+            log_warn!("sleep without running timer ignored");
+            return;
+        }
+        while !self.expired() {
+            interrupts::enable_and_hlt();
+        }
+    }
+}
+
+pub fn sleep_ms(ms: u64) {
+    Deadline::after_ms(ms).wait();
+}
@@ -0,0 +1,52 @@
+// =============================================================================
+// DATA I CZAS KALENDARZOWY
+// =============================================================================
+// Wspólny typ zegara RTC (`rtc.rs`) i znaczników czasu FAT16. Czas lokalny
+// bez strefy - tak liczy go RTC w PC i tak zapisuje go FAT.
+// Moduł nie zależy od reszty jądra: mkimage dołącza go przez `#[path]`.
+// =============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTime {
+    pub year: u16,
+    pub month: u8,  // 1-12
+    pub day: u8,    // 1-31
+    pub hour: u8,   // 0-23
+    pub minute: u8,
+    pub second: u8,
+}
+
+fn is_leap_year(year: u16) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl DateTime {
+    // Odrzuca np. 31 kwietnia albo godzinę 24 (śmieci z nieustawionego RTC)
+    pub fn is_valid(&self) -> bool {
+        (1..=12).contains(&self.month)
+            && self.day >= 1
+            && self.day <= days_in_month(self.year, self.month)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 60
+    }
+}
+
+impl core::fmt::Display for DateTime {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
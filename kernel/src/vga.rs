@@ -90,6 +90,46 @@ pub fn print_bytes(col: u64, row: u64, color: u8, bytes: &[u8]) {
     }
 }
 
+// Bufor jednej linii ekranu dla write!() - nadmiar jest obcinany, '\n' pomijany
+pub struct LineBuf {
+    buf: [u8; 80],
+    len: usize,
+}
+
+impl Default for LineBuf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LineBuf {
+    pub const fn new() -> Self {
+        LineBuf { buf: [b' '; 80], len: 0 }
+    }
+
+    // Sam wpisany tekst
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    // Cała linia dopełniona spacjami (nadpisuje poprzednią zawartość wiersza)
+    pub fn padded(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+impl core::fmt::Write for LineBuf {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for &b in s.as_bytes() {
+            if self.len < self.buf.len() && b != b'\n' {
+                self.buf[self.len] = b;
+                self.len += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
 pub fn set_cursor(col: u16, row: u16) {
     const SCREEN_COLS: u16 = 80;
     const SCREEN_ROWS: u16 = 25;
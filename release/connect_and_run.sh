@@ -1,6 +1,19 @@
-cat boot.bin kernel.bin > os.img
+# os.img = boot sector + jądro + partycja FAT16 z plikami z katalogu files/.
+# Obraz budujemy od nowa tylko gdy zmienił się bootloader, jądro lub pliki -
+# inaczej zapisy z edytora przetrwałyby tylko do następnego uruchomienia.
+# boot.bin składamy z boot/boot.asm przy każdym uruchomieniu - mkimage wpisuje
+# do niego rozmiar jądra (offset 444), stary bootloader ucinałby jądro na 64 KB.
+# Podmieniamy go tylko, gdy się zmienił, żeby nie przebudowywać obrazu.
+nasm ../boot/boot.asm -f bin -o boot.bin.new || exit 1
+if cmp -s boot.bin.new boot.bin; then rm boot.bin.new; else mv boot.bin.new boot.bin; fi
+if [ ! -f os.img ] || [ boot.bin -nt os.img ] || [ kernel.bin -nt os.img ] \
+   || [ -n "$(find files -newer os.img 2>/dev/null)" ]; then
+  cargo run --release --manifest-path ../kernel/Cargo.toml -p mkimage -- \
+    --boot boot.bin --kernel kernel.bin --files files -o os.img
+fi
 ls -lh os.img
 qemu-system-x86_64 \
   -machine accel=tcg \
   -drive format=raw,file=os.img \
+  -serial stdio \
   -no-shutdown
\ No newline at end of file